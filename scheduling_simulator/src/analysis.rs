@@ -0,0 +1,35 @@
+pub mod response_time;
+pub mod schedulability_test;
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::collections::BTreeMap;
+
+    use petgraph::graph::{Graph, NodeIndex};
+
+    use crate::task::dag::{Node, DAG};
+
+    /// A fork-join DAG with the given execution times of the source, the parallel nodes and the sink.
+    pub(crate) fn create_fork_join_dag(
+        dag_id: i32,
+        period: i32,
+        execution_times: &[i32],
+    ) -> Graph<Node, i32> {
+        let mut dag = Graph::<Node, i32>::new();
+        for (id, &execution_time) in execution_times.iter().enumerate() {
+            dag.add_node(Node::new(
+                id as i32,
+                BTreeMap::from([("execution_time".to_string(), execution_time)]),
+            ));
+        }
+        let sink_i = NodeIndex::new(execution_times.len() - 1);
+        for parallel_i in 1..execution_times.len() - 1 {
+            dag.add_edge(NodeIndex::new(0), NodeIndex::new(parallel_i), 0);
+            dag.add_edge(NodeIndex::new(parallel_i), sink_i, 0);
+        }
+        dag.set_param_to_all_nodes("dag_id", dag_id);
+        dag.set_param_to_all_nodes("period", period);
+        dag.set_param_to_all_nodes("relative_deadline", period);
+        dag
+    }
+}
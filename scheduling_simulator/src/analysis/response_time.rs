@@ -0,0 +1,297 @@
+//! Response-time analysis of DAG tasks under global scheduling on identical cores, based on the workload bounds of
+//! Melani et al., "Response-Time Analysis of Conditional DAG Tasks in Multiprocessor Systems" (ECRTS 2015).
+//!
+//! The bound of a sink is the execution time and the edge delays of a path to it, plus the rest of its DAG and the
+//! interfering workload of the other DAGs spread over the cores. The delays do not occupy the cores, so the path is
+//! the one that maximizes the bound rather than the longest one. Conditional DAGs are analyzed as if all branches
+//! execute, which is safe. Release jitter is added to the response time and to the interfering windows.
+//! The analysis assumes full preemption and no overheads.
+//!
+//! The response times of all DAGs are iterated together until they reach a fixed point. A sink has no bound if its
+//! response time exceeds its relative deadline or the period, because the workload bounds assume that jobs of the same
+//! DAG do not overlap. A DAG whose sinks are not all bounded makes the DAGs that it interferes with unbounded as well.
+
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use petgraph::graph::Graph;
+
+use crate::task::dag::{Node, DAG};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum AnalyzedPolicy {
+    /// Global fixed-priority scheduling with rate-monotonic priorities, i.e., `FixedPriorityScheduler` with the period
+    /// of each DAG as its priority. DAGs with the same period interfere with each other.
+    Rm,
+    /// `GlobalEDFScheduler`, where each node takes the earliest deadline among the sinks it reaches.
+    Edf,
+}
+
+struct AnalyzedDAG {
+    period: i32,
+    /// The earliest relative deadline among the sinks, which is the earliest deadline that its nodes may take.
+    min_relative_deadline: i32,
+    release_jitter: i32,
+    volume: i32,
+    /// (sink id, execution time and delay of the critical path to the sink, relative deadline)
+    sinks: Vec<(usize, CriticalPath, i32)>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct CriticalPath {
+    execution_time: i32,
+    delay: i32,
+}
+
+impl CriticalPath {
+    /// Part of the bound that depends on the path: the path itself plus the rest of the volume spread over the cores,
+    /// scaled by `num_cores` to stay an integer.
+    fn get_weight(&self, num_cores: i32) -> i64 {
+        (num_cores as i64 - 1) * self.execution_time as i64 + num_cores as i64 * self.delay as i64
+    }
+}
+
+/// The path to each node that maximizes `execution time + delay + (volume - execution time) / num_cores`.
+fn get_critical_paths(dag: &Graph<Node, i32>, num_cores: i32) -> Vec<CriticalPath> {
+    let mut critical_paths = vec![CriticalPath::default(); dag.node_count()];
+    for node_i in petgraph::algo::toposort(dag, None).expect("The graph is not a DAG.") {
+        let pre_path = dag
+            .get_pre(node_i)
+            .into_iter()
+            .map(|pre_i| {
+                let pre_path = critical_paths[pre_i.index()];
+                CriticalPath {
+                    execution_time: pre_path.execution_time,
+                    delay: pre_path.delay + dag[dag.find_edge(pre_i, node_i).unwrap()],
+                }
+            })
+            .max_by_key(|path| path.get_weight(num_cores))
+            .unwrap_or_default();
+        critical_paths[node_i.index()] = CriticalPath {
+            execution_time: pre_path.execution_time + dag[node_i].get_value("execution_time"),
+            delay: pre_path.delay,
+        };
+    }
+
+    critical_paths
+}
+
+impl AnalyzedDAG {
+    fn new(dag: &Graph<Node, i32>, num_cores: usize) -> Self {
+        let critical_paths = get_critical_paths(dag, num_cores as i32);
+        let sinks = dag
+            .get_sink()
+            .into_iter()
+            .map(|sink_i| {
+                (
+                    dag[sink_i].id as usize,
+                    critical_paths[sink_i.index()],
+                    dag[sink_i].get_value("relative_deadline"),
+                )
+            })
+            .collect::<Vec<_>>();
+        Self {
+            period: dag.get_dag_param("period"),
+            min_relative_deadline: sinks
+                .iter()
+                .map(|&(_, _, deadline)| deadline)
+                .min()
+                .unwrap(),
+            release_jitter: dag.get_dag_param_or("release_jitter", 0),
+            volume: dag.get_volume(),
+            sinks,
+        }
+    }
+
+    /// Upper bound on the workload of the DAG in a window of `length`, given its response time (Melani et al., Eq. 6).
+    fn get_workload(&self, length: i32, response_time: i32, num_cores: f64) -> f64 {
+        let volume = self.volume as f64;
+        let period = self.period as f64;
+        let window = (length + self.release_jitter + response_time) as f64 - volume / num_cores;
+        let num_jobs = (window / period).floor();
+        num_jobs * volume
+            + volume
+                .min(num_cores * (window - num_jobs * period))
+                .max(0.0)
+    }
+
+    /// Upper bound on the workload of the DAG that has earlier deadlines than a job whose deadline is at the end of
+    /// a window of `length` under EDF (Melani et al., Eq. 9).
+    fn get_edf_workload(&self, length: i32, response_time: i32, num_cores: f64) -> f64 {
+        let volume = self.volume as f64;
+        let window = length + self.release_jitter;
+        let num_jobs = window / self.period;
+        let carry_in = window % self.period - self.min_relative_deadline + response_time;
+        num_jobs as f64 * volume + volume.min(num_cores * carry_in.max(0) as f64)
+    }
+}
+
+/// Returns the response-time bound of each sink (node id -> bound) for each DAG in the order of `dag_set`.
+/// `None` means that the analysis cannot show that the sink meets its deadline.
+pub fn analyze_response_times(
+    dag_set: &[Graph<Node, i32>],
+    num_cores: usize,
+    policy: AnalyzedPolicy,
+) -> Vec<BTreeMap<usize, Option<i32>>> {
+    let dags = dag_set
+        .iter()
+        .map(|dag| AnalyzedDAG::new(dag, num_cores))
+        .collect::<Vec<_>>();
+    let num_cores_f64 = num_cores as f64;
+    let interferes = |interfering: &AnalyzedDAG, analyzed: &AnalyzedDAG| match policy {
+        AnalyzedPolicy::Rm => interfering.period <= analyzed.period,
+        AnalyzedPolicy::Edf => true,
+    };
+
+    let mut bounds = dags
+        .iter()
+        .map(|dag| {
+            dag.sinks
+                .iter()
+                .map(|&(_, path, _)| Some(dag.release_jitter + path.execution_time + path.delay))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let get_response_time = |bounds: &[Option<i32>]| -> Option<i32> {
+        bounds
+            .iter()
+            .copied()
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .max()
+    };
+
+    // The bounds only increase or become None, and they are capped, so the iteration terminates.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (dag_i, dag) in dags.iter().enumerate() {
+            for (sink_i, &(_, path, relative_deadline)) in dag.sinks.iter().enumerate() {
+                let Some(bound) = bounds[dag_i][sink_i] else {
+                    continue;
+                };
+                let interference = dags
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other_i, other)| other_i != dag_i && interferes(other, dag))
+                    .map(|(other_i, other)| {
+                        let response_time = get_response_time(&bounds[other_i])?;
+                        let workload = other.get_workload(bound, response_time, num_cores_f64);
+                        Some(match policy {
+                            AnalyzedPolicy::Rm => workload,
+                            AnalyzedPolicy::Edf => workload.min(other.get_edf_workload(
+                                relative_deadline,
+                                response_time,
+                                num_cores_f64,
+                            )),
+                        })
+                    })
+                    .sum::<Option<f64>>();
+                let new_bound = interference
+                    .map(|interference| {
+                        dag.release_jitter
+                            + path.execution_time
+                            + path.delay
+                            + (((dag.volume - path.execution_time) as f64 + interference)
+                                / num_cores_f64)
+                                .ceil() as i32
+                    })
+                    .filter(|&new_bound| new_bound <= relative_deadline.min(dag.period));
+                if new_bound != Some(bound) {
+                    bounds[dag_i][sink_i] = new_bound;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    dags.iter()
+        .zip(bounds)
+        .map(|(dag, bounds)| {
+            dag.sinks
+                .iter()
+                .map(|&(sink_id, _, _)| sink_id)
+                .zip(bounds)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_response_time {
+    use super::*;
+    use crate::analysis::test_util::create_fork_join_dag;
+    use petgraph::graph::NodeIndex;
+
+    // A fork-join DAG with two parallel nodes and a delay of 1 on the edge from the source to node 2.
+    fn create_delayed_fork_join_dag(
+        dag_id: i32,
+        period: i32,
+        execution_times: [i32; 4],
+    ) -> Graph<Node, i32> {
+        let mut dag = create_fork_join_dag(dag_id, period, &execution_times);
+        let edge_i = dag.find_edge(NodeIndex::new(0), NodeIndex::new(2)).unwrap();
+        dag[edge_i] = 1;
+        dag
+    }
+
+    #[test]
+    fn test_analyze_single_dag() {
+        // The path through node 2 has the delay: 4 + 1 + ceil((7 - 4) / 2), which is more than 5 + ceil((7 - 5) / 2)
+        // through node 1.
+        let dag_set = [create_delayed_fork_join_dag(0, 20, [1, 3, 2, 1])];
+        for policy in [AnalyzedPolicy::Rm, AnalyzedPolicy::Edf] {
+            let bounds = analyze_response_times(&dag_set, 2, policy);
+            assert_eq!(bounds, vec![BTreeMap::from([(3, Some(7))])]);
+        }
+    }
+
+    #[test]
+    fn test_analyze_delayed_chain() {
+        let mut dag = Graph::<Node, i32>::new();
+        for id in 0..2 {
+            dag.add_node(Node::new(
+                id,
+                BTreeMap::from([("execution_time".to_string(), 1)]),
+            ));
+        }
+        dag.add_edge(NodeIndex::new(0), NodeIndex::new(1), 10);
+        dag.set_param_to_all_nodes("dag_id", 0);
+        dag.set_param_to_all_nodes("period", 20);
+        dag.set_param_to_all_nodes("relative_deadline", 20);
+
+        // The delay does not occupy the core, so it is not spread over the cores: 1 + 10 + 1.
+        let bounds = analyze_response_times(&[dag], 1, AnalyzedPolicy::Rm);
+        assert_eq!(bounds, vec![BTreeMap::from([(1, Some(12))])]);
+    }
+
+    #[test]
+    fn test_analyze_rm_interference() {
+        let dag_set = [
+            create_delayed_fork_join_dag(0, 10, [1, 3, 2, 1]),
+            create_delayed_fork_join_dag(1, 30, [2, 4, 4, 2]),
+        ];
+        let bounds = analyze_response_times(&dag_set, 2, AnalyzedPolicy::Rm);
+        // DAG 0 has the highest priority and is not interfered with.
+        assert_eq!(bounds[0][&3], Some(7));
+        // DAG 1: 8 + 1 + ceil((12 - 8 + W_0(R_1)) / 2) with R_0 = 7 converges at 22, where W_0(22) = 2 * 7 + min(7, 2 * 5.5).
+        assert_eq!(bounds[1][&3], Some(22));
+
+        // Without enough cores, DAG 1 cannot meet its deadline.
+        let bounds = analyze_response_times(&dag_set, 1, AnalyzedPolicy::Rm);
+        assert_eq!(bounds[0][&3], Some(8));
+        assert_eq!(bounds[1][&3], None);
+    }
+
+    #[test]
+    fn test_analyze_edf_unbounded_interferer() {
+        let dag_set = [
+            create_delayed_fork_join_dag(0, 10, [1, 3, 2, 1]),
+            create_delayed_fork_join_dag(1, 20, [2, 4, 4, 2]),
+        ];
+        // Under EDF, DAG 0 is also interfered with by DAG 1, so neither is bounded on a single core.
+        let bounds = analyze_response_times(&dag_set, 1, AnalyzedPolicy::Edf);
+        assert_eq!(bounds[0][&3], None);
+        assert_eq!(bounds[1][&3], None);
+    }
+}
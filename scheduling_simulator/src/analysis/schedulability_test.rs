@@ -0,0 +1,335 @@
+//! Sufficient schedulability tests of DAG sets on identical cores, except for the density test, which is a heuristic.
+//! A rejection only means that the test cannot guarantee the deadlines, not that a deadline is missed.
+//!
+//! The deadline of a DAG is the earliest deadline among its sinks. Tests of results for implicit deadlines use
+//! min(deadline, period) as both the deadline and the period, which only makes the DAG more demanding, so the
+//! utilizations of these results are densities here. The critical path of the capacity augmentation bound includes
+//! the edge delays.
+
+use std::fmt;
+
+use clap::ValueEnum;
+use petgraph::graph::Graph;
+
+use super::response_time::{analyze_response_times, AnalyzedPolicy};
+use crate::scheduler::federated_scheduler::allocate_cores;
+use crate::scheduler::partitioning::{choose_core, BinPackingHeuristic};
+use crate::task::dag::{Node, DAG};
+
+/// Capacity augmentation bound of GEDF for DAG tasks, (3 + sqrt(5)) / 2 (Li et al., ECRTS 2014).
+const GEDF_CAPACITY_AUGMENTATION_BOUND: f64 = 2.618_034;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SchedulabilityTest {
+    /// Federated scheduling (Li et al., ECRTS 2014). Heavy DAGs get dedicated cores as in `FederatedScheduler`, and
+    /// light DAGs must fit on the remaining cores as sequential tasks, packed by first-fit decreasing density.
+    Federated,
+    /// Capacity augmentation bound of GEDF: the total density is at most m / 2.618 and each critical path is at most
+    /// 1 / 2.618 of its deadline.
+    GedfCapacityAugmentation,
+    /// Response-time analysis of global FP with RM priorities (Melani et al., ECRTS 2015). See `analysis::response_time`.
+    GfpResponseTime,
+    /// Density test of GEDF (Goossens, Funk and Baruah, 2003) with each DAG as a sequential task of its volume:
+    /// the total density is at most m - (m - 1) * the maximum density. The test is proven for sequential tasks only,
+    /// while the nodes of a DAG may run in parallel under GEDF, so it is a heuristic and not a sufficient test.
+    Density,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub accepted: bool,
+    pub reason: String,
+}
+
+impl TestResult {
+    fn accept(reason: String) -> Self {
+        Self {
+            accepted: true,
+            reason,
+        }
+    }
+
+    fn reject(reason: String) -> Self {
+        Self {
+            accepted: false,
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = if self.accepted {
+            "Accepted"
+        } else {
+            "Rejected"
+        };
+        write!(f, "{}: {}", verdict, self.reason)
+    }
+}
+
+/// min(the earliest sink deadline, period)
+fn get_constrained_deadline(dag: &Graph<Node, i32>) -> i32 {
+    dag.get_sink()
+        .into_iter()
+        .map(|sink_i| dag[sink_i].get_value("relative_deadline"))
+        .min()
+        .unwrap()
+        .min(dag.get_dag_param("period"))
+}
+
+fn get_density(dag: &Graph<Node, i32>) -> f64 {
+    dag.get_volume() as f64 / get_constrained_deadline(dag) as f64
+}
+
+fn test_federated(dag_set: &[Graph<Node, i32>], num_cores: usize) -> TestResult {
+    let allocated_cores = match allocate_cores(dag_set, num_cores) {
+        Ok(allocated_cores) => allocated_cores,
+        Err(err) => return TestResult::reject(err.to_string()),
+    };
+
+    let is_light = |dag: &Graph<Node, i32>| dag.get_volume() <= get_constrained_deadline(dag);
+    let num_dedicated_cores = dag_set
+        .iter()
+        .zip(allocated_cores.iter())
+        .filter(|(dag, _)| !is_light(dag))
+        .map(|(_, cores)| cores.len())
+        .sum::<usize>();
+    let num_shared_cores = num_cores - num_dedicated_cores;
+
+    let mut light_dags = dag_set
+        .iter()
+        .filter(|dag| is_light(dag))
+        .collect::<Vec<_>>();
+    light_dags.sort_by(|a, b| get_density(b).total_cmp(&get_density(a)));
+    let mut core_densities = vec![0.0; num_shared_cores];
+    for dag in light_dags.iter() {
+        let density = get_density(dag) as f32;
+        let Some(core_i) = choose_core(
+            &core_densities,
+            &vec![1.0; num_shared_cores],
+            density,
+            None,
+            BinPackingHeuristic::Ffd,
+        ) else {
+            return TestResult::reject(format!(
+                "Light DAG {} (density: {:.3}) does not fit on the {} shared cores.",
+                dag.get_dag_param("dag_id"),
+                density,
+                num_shared_cores
+            ));
+        };
+        core_densities[core_i] += density;
+    }
+
+    TestResult::accept(format!(
+        "Heavy DAGs use {} dedicated cores, and {} light DAGs fit on the {} shared cores.",
+        num_dedicated_cores,
+        light_dags.len(),
+        num_shared_cores
+    ))
+}
+
+fn test_gedf_capacity_augmentation(dag_set: &[Graph<Node, i32>], num_cores: usize) -> TestResult {
+    let bound = GEDF_CAPACITY_AUGMENTATION_BOUND;
+    for dag in dag_set {
+        let critical_path_length = dag.get_critical_path_length_with_delays();
+        let deadline = get_constrained_deadline(dag);
+        if critical_path_length as f64 > deadline as f64 / bound {
+            return TestResult::reject(format!(
+                "The critical path length {} of DAG {} exceeds its deadline {} / {:.3}.",
+                critical_path_length,
+                dag.get_dag_param("dag_id"),
+                deadline,
+                bound
+            ));
+        }
+    }
+
+    let total_density = dag_set.iter().map(get_density).sum::<f64>();
+    let capacity = num_cores as f64 / bound;
+    if total_density > capacity {
+        TestResult::reject(format!(
+            "The total density {:.3} exceeds {} cores / {:.3} = {:.3}.",
+            total_density, num_cores, bound, capacity
+        ))
+    } else {
+        TestResult::accept(format!(
+            "The total density {:.3} is at most {} cores / {:.3} = {:.3}, and every critical path is at most \
+             1 / {:.3} of its deadline.",
+            total_density, num_cores, bound, capacity, bound
+        ))
+    }
+}
+
+fn test_gfp_response_time(dag_set: &[Graph<Node, i32>], num_cores: usize) -> TestResult {
+    let bounds = analyze_response_times(dag_set, num_cores, AnalyzedPolicy::Rm);
+    let mut max_ratio = (0.0, 0, 0);
+    for (dag, bounds) in dag_set.iter().zip(bounds) {
+        let dag_id = dag.get_dag_param("dag_id");
+        for (sink_id, bound) in bounds {
+            let Some(bound) = bound else {
+                return TestResult::reject(format!(
+                    "The response time of sink {} of DAG {} cannot be bounded within its deadline.",
+                    sink_id, dag_id
+                ));
+            };
+            let sink_i = dag
+                .node_indices()
+                .find(|&node_i| dag[node_i].id as usize == sink_id)
+                .unwrap();
+            let ratio = bound as f64 / dag[sink_i].get_value("relative_deadline") as f64;
+            if ratio > max_ratio.0 {
+                max_ratio = (ratio, sink_id, dag_id);
+            }
+        }
+    }
+
+    let (ratio, sink_id, dag_id) = max_ratio;
+    TestResult::accept(format!(
+        "All sinks are bounded within their deadlines. The largest ratio of the bound to the deadline is {:.3} \
+         (sink {} of DAG {}).",
+        ratio, sink_id, dag_id
+    ))
+}
+
+fn test_density(dag_set: &[Graph<Node, i32>], num_cores: usize) -> TestResult {
+    let densities = dag_set.iter().map(get_density).collect::<Vec<_>>();
+    let (max_dag_i, &max_density) = densities
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .unwrap();
+    if max_density > 1.0 {
+        return TestResult::reject(format!(
+            "DAG {} has a density of {:.3}, which exceeds 1 as a sequential task.",
+            dag_set[max_dag_i].get_dag_param("dag_id"),
+            max_density
+        ));
+    }
+
+    let total_density = densities.iter().sum::<f64>();
+    let capacity = num_cores as f64 - (num_cores - 1) as f64 * max_density;
+    if total_density > capacity {
+        TestResult::reject(format!(
+            "The total density {:.3} exceeds m - (m - 1) * {:.3} = {:.3}.",
+            total_density, max_density, capacity
+        ))
+    } else {
+        TestResult::accept(format!(
+            "The total density {:.3} is at most m - (m - 1) * {:.3} = {:.3} (heuristic for DAGs).",
+            total_density, max_density, capacity
+        ))
+    }
+}
+
+impl SchedulabilityTest {
+    pub fn run(&self, dag_set: &[Graph<Node, i32>], num_cores: usize) -> TestResult {
+        match self {
+            SchedulabilityTest::Federated => test_federated(dag_set, num_cores),
+            SchedulabilityTest::GedfCapacityAugmentation => {
+                test_gedf_capacity_augmentation(dag_set, num_cores)
+            }
+            SchedulabilityTest::GfpResponseTime => test_gfp_response_time(dag_set, num_cores),
+            SchedulabilityTest::Density => test_density(dag_set, num_cores),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_schedulability_test {
+    use super::*;
+    use crate::analysis::test_util::create_fork_join_dag;
+
+    #[test]
+    fn test_federated() {
+        // DAG 0 is heavy and needs ceil((14 - 6) / (10 - 6)) = 2 cores. DAGs 1 and 2 are light with densities 0.5.
+        let dag_set = [
+            create_fork_join_dag(0, 10, &[1, 4, 4, 4, 1]),
+            create_fork_join_dag(1, 20, &[2, 6, 2]),
+            create_fork_join_dag(2, 20, &[2, 6, 2]),
+        ];
+        assert!(SchedulabilityTest::Federated.run(&dag_set, 3).accepted);
+        assert!(!SchedulabilityTest::Federated.run(&dag_set, 2).accepted);
+
+        // A third light DAG does not fit on the shared core.
+        let mut dag_set = dag_set.to_vec();
+        dag_set.push(create_fork_join_dag(3, 20, &[2, 6, 2]));
+        let result = SchedulabilityTest::Federated.run(&dag_set, 3);
+        assert!(!result.accepted);
+        assert!(result.reason.starts_with("Light DAG"));
+        assert!(SchedulabilityTest::Federated.run(&dag_set, 4).accepted);
+
+        // A chain longer than its period needs a dedicated core for each of its ceil(60 / 20) = 3 jobs in flight.
+        let mut chain = create_fork_join_dag(0, 20, &[30]);
+        chain.set_param_to_all_nodes("relative_deadline", 60);
+        assert!(
+            SchedulabilityTest::Federated
+                .run(&[chain.clone()], 3)
+                .accepted
+        );
+        assert!(!SchedulabilityTest::Federated.run(&[chain], 2).accepted);
+    }
+
+    #[test]
+    fn test_gedf_capacity_augmentation() {
+        // The critical paths 4 and 3 are at most 20 / 2.618 and 10 / 2.618, and the total density 0.7 is at most
+        // 2 / 2.618.
+        let dag_set = [
+            create_fork_join_dag(0, 20, &[1, 2, 2, 1]),
+            create_fork_join_dag(1, 10, &[1, 1, 1, 1]),
+        ];
+        assert!(
+            SchedulabilityTest::GedfCapacityAugmentation
+                .run(&dag_set, 2)
+                .accepted
+        );
+        // 0.7 exceeds 1 / 2.618.
+        assert!(
+            !SchedulabilityTest::GedfCapacityAugmentation
+                .run(&dag_set, 1)
+                .accepted
+        );
+
+        // The critical path 5 exceeds 10 / 2.618.
+        let dag_set = [create_fork_join_dag(0, 10, &[1, 3, 1])];
+        let result = SchedulabilityTest::GedfCapacityAugmentation.run(&dag_set, 8);
+        assert!(!result.accepted);
+        assert!(result.reason.starts_with("The critical path length 5"));
+    }
+
+    #[test]
+    fn test_gfp_response_time() {
+        let dag_set = [
+            create_fork_join_dag(0, 10, &[1, 3, 2, 1]),
+            create_fork_join_dag(1, 20, &[2, 4, 4, 2]),
+        ];
+        assert!(
+            SchedulabilityTest::GfpResponseTime
+                .run(&dag_set, 2)
+                .accepted
+        );
+        let result = SchedulabilityTest::GfpResponseTime.run(&dag_set, 1);
+        assert!(!result.accepted);
+        assert!(result.reason.contains("sink 3 of DAG 1"));
+    }
+
+    #[test]
+    fn test_density() {
+        // Densities 0.6 and 0.4: 1.0 <= 2 - 1 * 0.6
+        let dag_set = [
+            create_fork_join_dag(0, 10, &[1, 2, 2, 1]),
+            create_fork_join_dag(1, 10, &[1, 1, 1, 1]),
+        ];
+        assert!(SchedulabilityTest::Density.run(&dag_set, 2).accepted);
+        // On a single core, the total density must be at most 1.
+        assert!(SchedulabilityTest::Density.run(&dag_set, 1).accepted);
+        let mut dag_set = dag_set.to_vec();
+        dag_set.push(create_fork_join_dag(2, 10, &[1, 1, 1]));
+        assert!(!SchedulabilityTest::Density.run(&dag_set, 1).accepted);
+
+        // A DAG with a density above 1 cannot run sequentially.
+        let dag_set = [create_fork_join_dag(0, 10, &[1, 8, 8, 1])];
+        assert!(!SchedulabilityTest::Density.run(&dag_set, 4).accepted);
+    }
+}
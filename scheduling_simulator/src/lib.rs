@@ -1,3 +1,4 @@
+pub mod analysis;
 pub mod log;
 pub mod processor;
 pub mod scheduler;
@@ -1,11 +1,15 @@
+pub mod chrome_trace;
+pub mod gantt_chart;
+pub mod response_time_report;
+
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::{self, OpenOptions},
-    i32,
     io::Write,
 };
 
 use crate::{
+    analysis::schedulability_test::TestResult,
     processor::core::ProcessResult,
     task::dag::{Node, DAG},
 };
@@ -13,16 +17,46 @@ use chrono::Utc;
 use log::info;
 use petgraph::Graph;
 use serde_derive::{Deserialize, Serialize};
+use serde_yaml::Mapping;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeadlineMiss {
+    pub job_id: i32,
+    pub tardiness: i32,
+    /// false if the sink had not finished by the end of the simulation.
+    pub finished: bool,
+}
+
+/// Deadline misses of a sink in the short log, which omits the list of misses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeadlineMissSummary {
+    pub num_misses: usize,
+    pub num_unfinished: usize,
+    pub max_tardiness: i32,
+}
+
+impl DeadlineMissSummary {
+    fn new(misses: &[DeadlineMiss]) -> Self {
+        Self {
+            num_misses: misses.len(),
+            num_unfinished: misses.iter().filter(|miss| !miss.finished).count(),
+            max_tardiness: misses.iter().map(|miss| miss.tardiness).max().unwrap_or(0),
+        }
+    }
+}
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGLog {
     dag_id: usize,
     release_times: Vec<i32>,
-    finish_times: HashMap<usize, Vec<i32>>, // sink_i -> finish_times
+    relative_deadline_per_sink: HashMap<usize, i32>, // sink_i -> relative deadline
+    finish_times: HashMap<usize, Vec<i32>>,          // sink_i -> finish_times
     pub response_times_per_sink: HashMap<usize, Vec<i32>>, // sink_i -> response_times
     best_response_time_per_sink: HashMap<usize, i32>,
     average_response_time_per_sink: HashMap<usize, f32>,
     worst_response_time_per_sink: HashMap<usize, i32>,
+    pub deadline_misses_per_sink: HashMap<usize, Vec<DeadlineMiss>>, // sink_i -> deadline misses
+    pub worst_blocking_time_per_node: HashMap<usize, i32>, // node_i -> worst blocking time
 }
 
 impl DAGLog {
@@ -30,14 +64,21 @@ impl DAGLog {
         Self {
             dag_id,
             release_times: Default::default(),
+            relative_deadline_per_sink: Default::default(),
             finish_times: Default::default(),
             response_times_per_sink: Default::default(),
             best_response_time_per_sink: Default::default(),
             average_response_time_per_sink: Default::default(),
             worst_response_time_per_sink: Default::default(),
+            deadline_misses_per_sink: Default::default(),
+            worst_blocking_time_per_node: Default::default(),
         }
     }
 
+    pub fn get_release_time(&self, job_id: i32) -> i32 {
+        self.release_times[job_id as usize]
+    }
+
     pub fn calc_response_times(&mut self) {
         for (&sink_i, rts) in &self.response_times_per_sink {
             let mut sum_rt = 0;
@@ -57,19 +98,67 @@ impl DAGLog {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum IntervalEnd {
+    Completed,
+    Preempted,
+    /// The node was still running at the end of the simulation.
+    Unfinished,
+}
+
+/// A period in which a node occupied a core, including the overheads charged to it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionInterval {
+    pub dag_id: i32,
+    pub job_id: i32,
+    pub node_id: i32,
+    pub start: i32,
+    pub end: i32,
+    pub end_reason: IntervalEnd,
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 struct CoreLog {
     core_id: usize,
-    total_proc_time: i32,
+    speed: f32,
+    total_proc_time: i32, // including total_overhead_time
+    total_overhead_time: i32,
     utilization: f32,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    trace: Vec<ExecutionInterval>,
+    #[serde(skip)]
+    running_interval: Option<ExecutionInterval>,
 }
 
 impl CoreLog {
     fn new(core_id: usize) -> Self {
         Self {
             core_id,
+            speed: 1.0,
             total_proc_time: Default::default(),
+            total_overhead_time: Default::default(),
             utilization: Default::default(),
+            trace: Default::default(),
+            running_interval: None,
+        }
+    }
+
+    fn start_interval(&mut self, node: &Node, start: i32) {
+        self.running_interval = Some(ExecutionInterval {
+            dag_id: node.get_value("dag_id"),
+            job_id: node.get_value("job_id"),
+            node_id: node.id,
+            start,
+            end: start,
+            end_reason: IntervalEnd::Unfinished,
+        });
+    }
+
+    fn end_interval(&mut self, end: i32, end_reason: IntervalEnd) {
+        if let Some(mut interval) = self.running_interval.take() {
+            interval.end = end;
+            interval.end_reason = end_reason;
+            self.trace.push(interval);
         }
     }
 
@@ -82,6 +171,10 @@ impl CoreLog {
 pub struct ProcessorLog {
     num_cores: usize,
     core_logs: Vec<CoreLog>,
+    total_overhead_time: i32,
+    /// Sum of the core speeds, i.e., the work the processor can do per unit of time.
+    total_capacity: f32,
+    /// Average of the core utilizations weighted by their speeds.
     average_utilization: f32,
     variance_utilization: f32,
 }
@@ -91,6 +184,8 @@ impl ProcessorLog {
         Self {
             num_cores,
             core_logs: (0..num_cores).map(CoreLog::new).collect(),
+            total_overhead_time: Default::default(),
+            total_capacity: num_cores as f32,
             average_utilization: Default::default(),
             variance_utilization: Default::default(),
         }
@@ -101,26 +196,37 @@ impl ProcessorLog {
             core_log.calculate_utilization(schedule_length);
         }
 
+        // A busy fast core does more work than a busy slow core, so each core is weighted by its speed.
         self.average_utilization = self
             .core_logs
             .iter()
-            .map(|core_log| core_log.utilization)
+            .map(|core_log| core_log.utilization * core_log.speed)
             .sum::<f32>()
-            / self.core_logs.len() as f32;
+            / self.total_capacity;
 
         self.variance_utilization = self
             .core_logs
             .iter()
-            .map(|core_log| (core_log.utilization - self.average_utilization).powi(2))
+            .map(|core_log| {
+                core_log.speed * (core_log.utilization - self.average_utilization).powi(2)
+            })
             .sum::<f32>()
-            / self.core_logs.len() as f32;
+            / self.total_capacity;
     }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGSetSchedulerLog {
+    pub seed: u64,
+    /// Target normalized utilization of `--normalized_utilizations`, which the periods were scaled to.
+    pub normalized_utilization: Option<f64>,
     pub deadline_missed: bool,
     pub missed_job_id: Option<i32>,
+    /// Ratio of missed deadlines over sink instances, i.e., a job of a DAG with several sinks counts once per sink.
+    pub miss_ratio: f32,
+    pub max_tardiness: i32,
+    pub tardiness_percentiles: BTreeMap<String, i32>,
+    pub max_blocking_time: i32,
     pub total_utilization: f32,
     pub dag_set_log: Vec<DAGLog>,
     pub processor_log: ProcessorLog,
@@ -131,12 +237,26 @@ impl DAGSetSchedulerLog {
         let total_utilization = dag_set.iter().map(|dag| dag.get_utilization()).sum::<f32>();
         let mut dag_set_log = Vec::with_capacity(dag_set.len());
         for dag in dag_set.iter() {
-            dag_set_log.push(DAGLog::new(dag.get_dag_param("dag_id") as usize));
+            let mut dag_log = DAGLog::new(dag.get_dag_param("dag_id") as usize);
+            for sink_i in dag.get_sink() {
+                if let Some(&relative_deadline) = dag[sink_i].params.get("relative_deadline") {
+                    dag_log
+                        .relative_deadline_per_sink
+                        .insert(dag[sink_i].id as usize, relative_deadline);
+                }
+            }
+            dag_set_log.push(dag_log);
         }
 
         Self {
+            seed: Default::default(),
+            normalized_utilization: None,
             deadline_missed: false,
             missed_job_id: None,
+            miss_ratio: Default::default(),
+            max_tardiness: Default::default(),
+            tardiness_percentiles: Default::default(),
+            max_blocking_time: Default::default(),
             total_utilization,
             dag_set_log,
             processor_log: ProcessorLog::new(num_cores),
@@ -147,36 +267,116 @@ impl DAGSetSchedulerLog {
         self.dag_set_log[dag_i].release_times.push(release_time);
     }
 
-    pub fn write_dag_finish_time(&mut self, dag_i: usize, sink_i: usize, finish_time: i32) -> i32 {
+    /// Jobs of the same DAG may finish out of order, so the response time is taken from the release of `job_id`.
+    pub fn write_dag_finish_time(
+        &mut self,
+        dag_i: usize,
+        sink_i: usize,
+        job_id: i32,
+        finish_time: i32,
+    ) -> i32 {
         let dag_log = &mut self.dag_set_log[dag_i];
-        let finish_times = dag_log.finish_times.entry(sink_i).or_default();
-        let response_time = finish_time - dag_log.release_times[finish_times.len()];
+        let response_time = finish_time - dag_log.get_release_time(job_id);
         dag_log
             .response_times_per_sink
             .entry(sink_i)
             .or_default()
             .push(response_time);
-        finish_times.push(finish_time);
+        dag_log
+            .finish_times
+            .entry(sink_i)
+            .or_default()
+            .push(finish_time);
 
         response_time
     }
 
-    pub fn write_processing_time(&mut self, process_result: &[ProcessResult]) {
-        let core_indices = process_result
-            .iter()
-            .enumerate()
-            .filter_map(|(index, result)| match result {
-                ProcessResult::InProgress => Some(index),
-                ProcessResult::Done(node_data) if !node_data.params.contains_key("dummy") => {
-                    Some(index)
+    pub fn write_deadline_miss(
+        &mut self,
+        dag_i: usize,
+        sink_i: usize,
+        job_id: i32,
+        tardiness: i32,
+        finished: bool,
+    ) {
+        self.dag_set_log[dag_i]
+            .deadline_misses_per_sink
+            .entry(sink_i)
+            .or_default()
+            .push(DeadlineMiss {
+                job_id,
+                tardiness,
+                finished,
+            });
+    }
+
+    pub fn write_blocking_time(&mut self, dag_i: usize, node_i: usize, blocking_time: i32) {
+        let worst_blocking_time = self.dag_set_log[dag_i]
+            .worst_blocking_time_per_node
+            .entry(node_i)
+            .or_default();
+        *worst_blocking_time = (*worst_blocking_time).max(blocking_time);
+        self.max_blocking_time = self.max_blocking_time.max(blocking_time);
+    }
+
+    pub fn write_processing_time(&mut self, process_result: &[ProcessResult], time: i32) {
+        for (core_i, result) in process_result.iter().enumerate() {
+            let proc_time = match result {
+                ProcessResult::Idle => continue,
+                ProcessResult::InProgress => time,
+                // The last time unit of a dummy node is not counted.
+                ProcessResult::Done(node_data) if node_data.params.contains_key("dummy") => {
+                    time - 1
                 }
-                _ => None,
-            })
-            .collect::<Vec<_>>();
+                ProcessResult::Done(_) => time,
+            };
+            self.processor_log.core_logs[core_i].total_proc_time += proc_time;
+        }
+    }
+
+    pub fn write_overhead_times(&mut self, total_overhead_times: &[i32]) {
+        for (core_log, &overhead_time) in self
+            .processor_log
+            .core_logs
+            .iter_mut()
+            .zip(total_overhead_times)
+        {
+            core_log.total_overhead_time = overhead_time;
+        }
+        self.processor_log.total_overhead_time = total_overhead_times.iter().sum();
+    }
 
-        for core_i in core_indices {
-            self.processor_log.core_logs[core_i].total_proc_time += 1;
+    pub fn write_core_speeds(&mut self, core_speeds: &[f64]) {
+        for (core_log, &speed) in self.processor_log.core_logs.iter_mut().zip(core_speeds) {
+            core_log.speed = speed as f32;
         }
+        self.processor_log.total_capacity = core_speeds.iter().sum::<f64>() as f32;
+    }
+
+    pub fn write_allocating_node(&mut self, core_i: usize, node: &Node, time: i32) {
+        self.processor_log.core_logs[core_i].start_interval(node, time);
+    }
+
+    pub fn write_preempted_node(&mut self, core_i: usize, time: i32) {
+        self.processor_log.core_logs[core_i].end_interval(time, IntervalEnd::Preempted);
+    }
+
+    pub fn write_finished_nodes(&mut self, process_result: &[ProcessResult], time: i32) {
+        for (core_i, result) in process_result.iter().enumerate() {
+            if let ProcessResult::Done(_) = result {
+                self.processor_log.core_logs[core_i].end_interval(time, IntervalEnd::Completed);
+            }
+        }
+    }
+
+    pub fn write_unfinished_nodes(&mut self, time: i32) {
+        for core_log in self.processor_log.core_logs.iter_mut() {
+            core_log.end_interval(time, IntervalEnd::Unfinished);
+        }
+    }
+
+    pub fn get_trace(&self, core_i: usize) -> &[ExecutionInterval] {
+        &self.processor_log.core_logs[core_i].trace
     }
 
     pub fn calc_response_times(&mut self) {
@@ -185,40 +385,279 @@ impl DAGSetSchedulerLog {
         }
     }
 
+    pub fn calc_deadline_miss_stats(&mut self) {
+        let mut tardiness = Vec::new();
+        let mut num_unfinished_misses = 0;
+        for miss in self
+            .dag_set_log
+            .iter()
+            .flat_map(|dag_log| dag_log.deadline_misses_per_sink.values().flatten())
+        {
+            tardiness.push(miss.tardiness);
+            if !miss.finished {
+                num_unfinished_misses += 1;
+            }
+        }
+        let num_finished_sinks = self
+            .dag_set_log
+            .iter()
+            .flat_map(|dag_log| dag_log.response_times_per_sink.values())
+            .map(|rts| rts.len())
+            .sum::<usize>();
+
+        // The ratio is taken over sink instances that either finished or passed their deadline.
+        let num_judged_sinks = num_finished_sinks + num_unfinished_misses;
+        self.miss_ratio = if num_judged_sinks == 0 {
+            0.0
+        } else {
+            tardiness.len() as f32 / num_judged_sinks as f32
+        };
+
+        tardiness.sort();
+        self.max_tardiness = tardiness.last().copied().unwrap_or(0);
+        self.tardiness_percentiles.clear();
+        if !tardiness.is_empty() {
+            for percentile in [50, 90, 95, 99] {
+                // Nearest-rank method
+                let rank = (percentile * tardiness.len()).div_ceil(100);
+                self.tardiness_percentiles
+                    .insert(format!("p{}", percentile), tardiness[rank - 1]);
+            }
+        }
+    }
+
     pub fn calculate_utilization(&mut self, schedule_length: i32) {
         self.processor_log.calc_utilization(schedule_length);
     }
 
+    /// The short log keeps the scalar statistics, the tardiness percentiles and a summary of the deadline misses of each
+    /// sink of each DAG that missed a deadline. The verbose log keeps every deadline miss.
     pub fn dump_to_yaml(&self, dir_path: &str, alg_name: &str, verbose: bool) {
-        let date = Utc::now().format("%Y-%m-%d-%H-%M-%S-%3f").to_string();
-        let file_name = format!("{}-{}-log", date, alg_name);
-        if fs::metadata(dir_path).is_err() {
-            let _ = fs::create_dir_all(dir_path);
-            info!("Directory created: {}", dir_path);
-        }
-        let file_path = format!("{}/{}.yaml", dir_path, file_name);
-        fs::File::create(&file_path).expect("Failed to create file.");
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(file_path)
-            .expect("Failed to open file.");
+        let mut file = create_log_file(dir_path, alg_name, "log.yaml");
         let yaml = if verbose {
             serde_yaml::to_string(&self).expect("Failed to serialize.")
         } else {
-            serde_yaml::to_string(&HashMap::from([
+            let mut mapping = Mapping::new();
+            for (key, value) in [
+                ("seed", self.seed.to_string()),
                 ("deadline_missed", self.deadline_missed.to_string()),
                 (
                     "missed_job_id",
                     self.missed_job_id.unwrap_or(i32::MAX).to_string(),
                 ),
+                ("miss_ratio", self.miss_ratio.to_string()),
+                ("max_tardiness", self.max_tardiness.to_string()),
+                ("max_blocking_time", self.max_blocking_time.to_string()),
                 ("total_utilization", self.total_utilization.to_string()),
                 ("num_cores", self.processor_log.num_cores.to_string()),
-            ]))
-            .expect("Failed to serialize.")
+                (
+                    "total_overhead_time",
+                    self.processor_log.total_overhead_time.to_string(),
+                ),
+            ] {
+                mapping.insert(key.into(), value.into());
+            }
+            if let Some(normalized_utilization) = self.normalized_utilization {
+                mapping.insert(
+                    "normalized_utilization".into(),
+                    normalized_utilization.to_string().into(),
+                );
+            }
+            // dag_id -> sink_i -> summary of the deadline misses
+            let deadline_misses_per_sink = self
+                .dag_set_log
+                .iter()
+                .filter(|dag_log| !dag_log.deadline_misses_per_sink.is_empty())
+                .map(|dag_log| {
+                    (
+                        dag_log.dag_id,
+                        dag_log
+                            .deadline_misses_per_sink
+                            .iter()
+                            .map(|(sink_i, misses)| (sink_i, DeadlineMissSummary::new(misses)))
+                            .collect::<BTreeMap<_, _>>(),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>();
+            mapping.insert(
+                "tardiness_percentiles".into(),
+                serde_yaml::to_value(&self.tardiness_percentiles).expect("Failed to serialize."),
+            );
+            mapping.insert(
+                "deadline_misses_per_sink".into(),
+                serde_yaml::to_value(deadline_misses_per_sink).expect("Failed to serialize."),
+            );
+            serde_yaml::to_string(&mapping).expect("Failed to serialize.")
         };
         file.write_all(yaml.as_bytes())
             .expect("Failed to write to file.");
     }
+
+    pub fn dump_chrome_trace_json(&self, dir_path: &str, alg_name: &str) {
+        let json = chrome_trace::create_chrome_trace_json(self);
+        create_log_file(dir_path, alg_name, "trace.json")
+            .write_all(json.as_bytes())
+            .expect("Failed to write to file.");
+    }
+
+    /// `bounds` are the response-time bounds of the sinks given by `analysis::response_time::analyze_response_times`.
+    pub fn dump_response_time_report(
+        &self,
+        dir_path: &str,
+        alg_name: &str,
+        bounds: &[BTreeMap<usize, Option<i32>>],
+    ) {
+        let yaml = response_time_report::create_response_time_report_yaml(self, bounds);
+        create_log_file(dir_path, alg_name, "rta.yaml")
+            .write_all(yaml.as_bytes())
+            .expect("Failed to write to file.");
+    }
+
+    pub fn dump_gantt_chart_svg(&self, dir_path: &str, alg_name: &str) {
+        let core_traces = self
+            .processor_log
+            .core_logs
+            .iter()
+            .map(|core_log| core_log.trace.as_slice())
+            .collect::<Vec<_>>();
+        let svg = gantt_chart::create_gantt_chart_svg(&core_traces);
+        create_log_file(dir_path, alg_name, "gantt.svg")
+            .write_all(svg.as_bytes())
+            .expect("Failed to write to file.");
+    }
+}
+
+/// Dump the verdict of an analytical schedulability test on a DAG set with the keys of the short scheduler log, where
+/// `deadline_missed` stands for a rejection, so that analytical and simulated acceptance ratios can be computed
+/// together.
+pub fn dump_schedulability_test_result(
+    dir_path: &str,
+    test_name: &str,
+    seed: u64,
+    dag_set: &[Graph<Node, i32>],
+    num_cores: usize,
+    result: &TestResult,
+) {
+    let total_utilization = dag_set.iter().map(|dag| dag.get_utilization()).sum::<f32>();
+    let yaml = serde_yaml::to_string(&HashMap::from([
+        ("seed", seed.to_string()),
+        ("deadline_missed", (!result.accepted).to_string()),
+        ("reason", result.reason.clone()),
+        ("total_utilization", total_utilization.to_string()),
+        ("num_cores", num_cores.to_string()),
+    ]))
+    .expect("Failed to serialize.");
+    create_log_file(dir_path, test_name, "schedulability.yaml")
+        .write_all(yaml.as_bytes())
+        .expect("Failed to write to file.");
+}
+
+/// Dump a run that `alg_name` could not simulate, e.g., because the periods could not be scaled to the target
+/// `normalized_utilization` or the DAG set could not be allocated to the cores, with the keys of the short scheduler
+/// log, as a deadline miss, so that the run still counts in the acceptance ratio.
+pub fn dump_unschedulable_run(
+    dir_path: &str,
+    alg_name: &str,
+    seed: u64,
+    normalized_utilization: Option<f64>,
+    total_utilization: f64,
+    num_cores: usize,
+    reason: &str,
+) {
+    let mut mapping = HashMap::from([
+        ("seed", seed.to_string()),
+        ("deadline_missed", true.to_string()),
+        ("reason", reason.to_string()),
+        ("total_utilization", total_utilization.to_string()),
+        ("num_cores", num_cores.to_string()),
+    ]);
+    if let Some(normalized_utilization) = normalized_utilization {
+        mapping.insert("normalized_utilization", normalized_utilization.to_string());
+    }
+    let yaml = serde_yaml::to_string(&mapping).expect("Failed to serialize.");
+    create_log_file(dir_path, alg_name, "log.yaml")
+        .write_all(yaml.as_bytes())
+        .expect("Failed to write to file.");
+}
+
+fn create_log_file(dir_path: &str, alg_name: &str, suffix: &str) -> fs::File {
+    let date = Utc::now().format("%Y-%m-%d-%H-%M-%S-%3f").to_string();
+    if fs::metadata(dir_path).is_err() {
+        let _ = fs::create_dir_all(dir_path);
+        info!("Directory created: {}", dir_path);
+    }
+    let file_path = format!("{}/{}-{}-{}", dir_path, date, alg_name, suffix);
+    fs::File::create(&file_path).expect("Failed to create file.");
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)
+        .expect("Failed to open file.")
+}
+
+#[cfg(test)]
+mod tests_log {
+    use super::*;
+
+    #[test]
+    fn test_write_dag_finish_time_out_of_order() {
+        let mut log = DAGSetSchedulerLog {
+            dag_set_log: vec![DAGLog::new(0)],
+            ..Default::default()
+        };
+        log.write_dag_release_time(0, 0);
+        log.write_dag_release_time(0, 2);
+
+        // Job 1 finishes before job 0.
+        assert_eq!(log.write_dag_finish_time(0, 1, 1, 5), 3);
+        assert_eq!(log.write_dag_finish_time(0, 1, 0, 6), 6);
+    }
+
+    #[test]
+    fn test_dump_short_log_with_deadline_misses() {
+        let mut log = DAGSetSchedulerLog {
+            dag_set_log: vec![DAGLog::new(0), DAGLog::new(1)],
+            ..Default::default()
+        };
+        log.write_deadline_miss(1, 3, 0, 4, true);
+        log.write_deadline_miss(1, 3, 2, 6, false);
+        log.calc_deadline_miss_stats();
+
+        let dir_path = std::env::temp_dir().join("test_dump_short_log_with_deadline_misses");
+        let _ = fs::remove_dir_all(&dir_path);
+        log.dump_to_yaml(dir_path.to_str().unwrap(), "test", false);
+        let file_path = fs::read_dir(&dir_path)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(&fs::read_to_string(file_path).unwrap()).unwrap();
+
+        assert_eq!(yaml["max_tardiness"], "6");
+        assert_eq!(yaml["tardiness_percentiles"]["p50"], 4);
+        assert_eq!(yaml["tardiness_percentiles"]["p99"], 6);
+        let misses = &yaml["deadline_misses_per_sink"][1][3];
+        assert_eq!(misses["num_misses"], 2);
+        assert_eq!(misses["num_unfinished"], 1);
+        assert_eq!(misses["max_tardiness"], 6);
+        assert!(yaml["deadline_misses_per_sink"].get(0).is_none());
+    }
+
+    #[test]
+    fn test_capacity_aware_utilization() {
+        let mut log = DAGSetSchedulerLog {
+            processor_log: ProcessorLog::new(2),
+            ..Default::default()
+        };
+        log.write_core_speeds(&[1.5, 0.5]);
+        log.write_processing_time(&[ProcessResult::InProgress, ProcessResult::Idle], 10);
+        log.calculate_utilization(10);
+
+        // The fast core is fully busy and has 3/4 of the capacity.
+        assert_eq!(log.processor_log.total_capacity, 2.0);
+        assert_eq!(log.processor_log.average_utilization, 0.75);
+    }
 }
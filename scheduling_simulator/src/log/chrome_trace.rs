@@ -0,0 +1,147 @@
+//! Chrome trace-event JSON of a simulated schedule, viewable in Perfetto and chrome://tracing.
+//! Each core is a thread of the "Processor" process and each `ExecutionInterval` is a duration slice.
+//! DAG releases and sink deadlines are instant events on a thread per DAG of the "DAGs" process.
+//! Timestamps are the simulation time as is, which is in microseconds for the Autoware workload.
+
+use serde_json::{json, Value};
+
+use super::DAGSetSchedulerLog;
+
+const PROCESSOR_PID: usize = 0;
+const DAG_PID: usize = 1;
+
+fn create_metadata_events(log: &DAGSetSchedulerLog) -> Vec<Value> {
+    let mut events = vec![
+        json!({"name": "process_name", "ph": "M", "pid": PROCESSOR_PID, "args": {"name": "Processor"}}),
+        json!({"name": "process_name", "ph": "M", "pid": DAG_PID, "args": {"name": "DAGs"}}),
+    ];
+    for core_log in log.processor_log.core_logs.iter() {
+        events.push(json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": PROCESSOR_PID,
+            "tid": core_log.core_id,
+            "args": {"name": format!("Core {}", core_log.core_id)},
+        }));
+    }
+    for dag_log in log.dag_set_log.iter() {
+        events.push(json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": DAG_PID,
+            "tid": dag_log.dag_id,
+            "args": {"name": format!("DAG {}", dag_log.dag_id)},
+        }));
+    }
+
+    events
+}
+
+pub fn create_chrome_trace_json(log: &DAGSetSchedulerLog) -> String {
+    let mut events = create_metadata_events(log);
+
+    for core_log in log.processor_log.core_logs.iter() {
+        for interval in core_log.trace.iter() {
+            events.push(json!({
+                "name": format!("DAG {} node {}", interval.dag_id, interval.node_id),
+                "cat": "node",
+                "ph": "X",
+                "ts": interval.start,
+                "dur": interval.end - interval.start,
+                "pid": PROCESSOR_PID,
+                "tid": core_log.core_id,
+                "args": {
+                    "dag_id": interval.dag_id,
+                    "job_id": interval.job_id,
+                    "node_id": interval.node_id,
+                    "end_reason": interval.end_reason,
+                },
+            }));
+        }
+    }
+
+    for dag_log in log.dag_set_log.iter() {
+        for (job_id, &release_time) in dag_log.release_times.iter().enumerate() {
+            events.push(json!({
+                "name": "release",
+                "cat": "release",
+                "ph": "i",
+                "s": "t",
+                "ts": release_time,
+                "pid": DAG_PID,
+                "tid": dag_log.dag_id,
+                "args": {"job_id": job_id},
+            }));
+
+            // HashMap order is not deterministic, so emit the deadlines in the order of sink ids.
+            let mut relative_deadlines = dag_log
+                .relative_deadline_per_sink
+                .iter()
+                .collect::<Vec<_>>();
+            relative_deadlines.sort();
+            for (&sink_i, &relative_deadline) in relative_deadlines {
+                events.push(json!({
+                    "name": format!("deadline (sink {})", sink_i),
+                    "cat": "deadline",
+                    "ph": "i",
+                    "s": "t",
+                    "ts": release_time + relative_deadline,
+                    "pid": DAG_PID,
+                    "tid": dag_log.dag_id,
+                    "args": {"job_id": job_id, "sink_id": sink_i},
+                }));
+            }
+        }
+    }
+
+    serde_json::to_string(&json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+    }))
+    .expect("Failed to serialize.")
+}
+
+#[cfg(test)]
+mod tests_chrome_trace {
+    use super::*;
+    use crate::log::{DAGLog, ExecutionInterval, IntervalEnd, ProcessorLog};
+
+    #[test]
+    fn test_create_chrome_trace_json() {
+        let mut dag_log = DAGLog::new(0);
+        dag_log.release_times = vec![0, 10];
+        dag_log.relative_deadline_per_sink.insert(2, 8);
+        let mut processor_log = ProcessorLog::new(1);
+        processor_log.core_logs[0].trace.push(ExecutionInterval {
+            dag_id: 0,
+            job_id: 1,
+            node_id: 2,
+            start: 10,
+            end: 15,
+            end_reason: IntervalEnd::Completed,
+        });
+        let log = DAGSetSchedulerLog {
+            dag_set_log: vec![dag_log],
+            processor_log,
+            ..Default::default()
+        };
+
+        let trace: Value = serde_json::from_str(&create_chrome_trace_json(&log)).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        // 2 process names, 1 core, 1 DAG, 1 slice, 2 releases and 2 deadlines
+        assert_eq!(events.len(), 9);
+
+        let slice = events.iter().find(|event| event["ph"] == "X").unwrap();
+        assert_eq!(slice["ts"], 10);
+        assert_eq!(slice["dur"], 5);
+        assert_eq!(slice["args"]["job_id"], 1);
+        assert_eq!(slice["args"]["end_reason"], "Completed");
+
+        let deadlines = events
+            .iter()
+            .filter(|event| event["cat"] == "deadline")
+            .map(|event| event["ts"].as_i64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(deadlines, vec![8, 18]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Static SVG Gantt chart of the execution trace. Each row is a core and each bar is an `ExecutionInterval`,
+//! colored by dag_id. Intervals that did not end with a completion are drawn with a dashed border.
+
+use std::fmt::Write;
+
+use super::{ExecutionInterval, IntervalEnd};
+
+const CHART_WIDTH: f64 = 1200.0;
+const LEFT_MARGIN: f64 = 70.0;
+const RIGHT_MARGIN: f64 = 20.0;
+const TOP_MARGIN: f64 = 20.0;
+const ROW_HEIGHT: f64 = 30.0;
+const ROW_GAP: f64 = 10.0;
+const AXIS_HEIGHT: f64 = 30.0;
+const NUM_TICKS: i32 = 10;
+const FONT_SIZE: f64 = 11.0;
+/// Rough width of a character, used to decide whether a label fits in its bar.
+const CHAR_WIDTH: f64 = 6.5;
+
+fn get_color(dag_id: i32) -> String {
+    // The golden angle spreads the hues of consecutive dag_ids.
+    format!("hsl({}, 60%, 65%)", (dag_id * 137).rem_euclid(360))
+}
+
+pub fn create_gantt_chart_svg(core_traces: &[&[ExecutionInterval]]) -> String {
+    let end_time = core_traces
+        .iter()
+        .flat_map(|trace| trace.iter().map(|interval| interval.end))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let scale = CHART_WIDTH / end_time as f64;
+    let x = |time: i32| LEFT_MARGIN + time as f64 * scale;
+    let rows_height = core_traces.len() as f64 * (ROW_HEIGHT + ROW_GAP);
+    let width = LEFT_MARGIN + CHART_WIDTH + RIGHT_MARGIN;
+    let height = TOP_MARGIN + rows_height + AXIS_HEIGHT;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="{FONT_SIZE}">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="white"/>"#
+    )
+    .unwrap();
+
+    for (core_i, trace) in core_traces.iter().enumerate() {
+        let y = TOP_MARGIN + core_i as f64 * (ROW_HEIGHT + ROW_GAP);
+        writeln!(
+            svg,
+            r#"<text x="5" y="{}" dominant-baseline="middle">Core {core_i}</text>"#,
+            y + ROW_HEIGHT / 2.0
+        )
+        .unwrap();
+
+        for interval in trace.iter() {
+            let bar_x = x(interval.start);
+            let bar_width = (interval.end - interval.start) as f64 * scale;
+            let dash = match interval.end_reason {
+                IntervalEnd::Completed => "",
+                IntervalEnd::Preempted | IntervalEnd::Unfinished => r#" stroke-dasharray="4 2""#,
+            };
+            writeln!(
+                svg,
+                r#"<rect x="{bar_x}" y="{y}" width="{bar_width}" height="{ROW_HEIGHT}" fill="{}" stroke="black"{dash}><title>DAG {}, job {}, node {}: [{}, {}) {:?}</title></rect>"#,
+                get_color(interval.dag_id),
+                interval.dag_id,
+                interval.job_id,
+                interval.node_id,
+                interval.start,
+                interval.end,
+                interval.end_reason
+            )
+            .unwrap();
+
+            let label = format!("{}-{}", interval.dag_id, interval.node_id);
+            if label.len() as f64 * CHAR_WIDTH < bar_width {
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="middle" pointer-events="none">{label}</text>"#,
+                    bar_x + bar_width / 2.0,
+                    y + ROW_HEIGHT / 2.0
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    // Time axis
+    let axis_y = TOP_MARGIN + rows_height;
+    writeln!(
+        svg,
+        r#"<line x1="{LEFT_MARGIN}" y1="{axis_y}" x2="{}" y2="{axis_y}" stroke="black"/>"#,
+        x(end_time)
+    )
+    .unwrap();
+    let tick_step = (end_time + NUM_TICKS - 1) / NUM_TICKS;
+    for tick in (0..=end_time).step_by(tick_step as usize) {
+        writeln!(
+            svg,
+            r#"<line x1="{0}" y1="{axis_y}" x2="{0}" y2="{1}" stroke="black"/><text x="{0}" y="{2}" text-anchor="middle">{tick}</text>"#,
+            x(tick),
+            axis_y + 5.0,
+            axis_y + 5.0 + FONT_SIZE + 2.0
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+#[cfg(test)]
+mod tests_gantt_chart {
+    use super::*;
+
+    #[test]
+    fn test_create_gantt_chart_svg() {
+        let core0_trace = vec![
+            ExecutionInterval {
+                dag_id: 0,
+                job_id: 0,
+                node_id: 1,
+                start: 0,
+                end: 40,
+                end_reason: IntervalEnd::Preempted,
+            },
+            ExecutionInterval {
+                dag_id: 1,
+                job_id: 2,
+                node_id: 3,
+                start: 40,
+                end: 100,
+                end_reason: IntervalEnd::Completed,
+            },
+        ];
+        let svg = create_gantt_chart_svg(&[&core0_trace, &[]]);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 2);
+        assert!(svg.contains("DAG 1, job 2, node 3: [40, 100) Completed"));
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert!(svg.contains("Core 1"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Comparison of the analytical response-time bounds of the sinks with their simulated worst response times.
+//! The simulated response times are lower bounds on the worst case, so the ratio of the bound to the simulated worst
+//! response time shows how pessimistic the analysis is, up to how pessimistic the simulation is.
+//! `bound_violated` means that the simulation exceeded the bound, which happens when the simulated configuration
+//! breaks the assumptions of the analysis, e.g., with overheads or limited preemption.
+
+use std::collections::BTreeMap;
+
+use serde_derive::Serialize;
+
+use super::DAGSetSchedulerLog;
+
+#[derive(Serialize)]
+struct SinkReport {
+    dag_id: usize,
+    sink_id: usize,
+    relative_deadline: Option<i32>,
+    /// None if the analysis cannot show that the sink meets its deadline.
+    bound: Option<i32>,
+    /// None if no job of the sink finished in the simulation.
+    simulated_worst_response_time: Option<i32>,
+    bound_to_simulated_ratio: Option<f32>,
+    bound_violated: bool,
+}
+
+pub fn create_response_time_report_yaml(
+    log: &DAGSetSchedulerLog,
+    bounds: &[BTreeMap<usize, Option<i32>>],
+) -> String {
+    let reports = log
+        .dag_set_log
+        .iter()
+        .zip(bounds)
+        .flat_map(|(dag_log, bounds)| {
+            bounds.iter().map(|(&sink_id, &bound)| {
+                let simulated_worst_response_time =
+                    dag_log.worst_response_time_per_sink.get(&sink_id).copied();
+                let simulated_and_bound = simulated_worst_response_time.zip(bound);
+                SinkReport {
+                    dag_id: dag_log.dag_id,
+                    sink_id,
+                    relative_deadline: dag_log.relative_deadline_per_sink.get(&sink_id).copied(),
+                    bound,
+                    simulated_worst_response_time,
+                    bound_to_simulated_ratio: simulated_and_bound
+                        .filter(|&(simulated, _)| simulated > 0)
+                        .map(|(simulated, bound)| bound as f32 / simulated as f32),
+                    bound_violated: simulated_and_bound
+                        .is_some_and(|(simulated, bound)| simulated > bound),
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_yaml::to_string(&reports).expect("Failed to serialize.")
+}
+
+#[cfg(test)]
+mod tests_response_time_report {
+    use super::*;
+    use crate::log::DAGLog;
+
+    #[test]
+    fn test_create_response_time_report_yaml() {
+        let mut dag_log = DAGLog::new(0);
+        dag_log.relative_deadline_per_sink.insert(2, 10);
+        dag_log.worst_response_time_per_sink.insert(2, 4);
+        dag_log.relative_deadline_per_sink.insert(3, 10);
+        dag_log.worst_response_time_per_sink.insert(3, 7);
+        let log = DAGSetSchedulerLog {
+            dag_set_log: vec![dag_log],
+            ..Default::default()
+        };
+        let bounds = [BTreeMap::from([(2, Some(6)), (3, Some(5))])];
+
+        let reports: Vec<serde_yaml::Value> =
+            serde_yaml::from_str(&create_response_time_report_yaml(&log, &bounds)).unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0]["bound"], 6);
+        assert_eq!(reports[0]["bound_to_simulated_ratio"], 1.5);
+        assert_eq!(reports[0]["bound_violated"], false);
+        assert_eq!(reports[1]["bound_violated"], true);
+    }
+}
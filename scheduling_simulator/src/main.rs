@@ -1,57 +1,477 @@
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use scheduling_simulator::{
-    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    analysis::{
+        response_time::{analyze_response_times, AnalyzedPolicy},
+        schedulability_test::SchedulabilityTest,
+    },
+    log::{dump_schedulability_test_result, dump_unschedulable_run},
+    processor::{
+        core::Overheads, heterogeneous::HeterogeneousProcessor, homogeneous::HomogeneousProcessor,
+    },
     scheduler::{
-        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        dag_set_scheduler::{
+            CommunicationDelayMode, DAGSetSchedulerBase, IntraTaskPrecedence, PreemptionMode,
+            PreemptiveType, ResourceProtocol, SchedulerConfig,
+        },
+        edzl_scheduler::EDZLScheduler,
+        federated_scheduler::FederatedScheduler,
         fixed_priority_scheduler::FixedPriorityScheduler,
+        llf_scheduler::GlobalLLFScheduler,
+        partitioning::{partition_dag_set_with_speeds, BinPackingHeuristic, PartitioningUnit},
         proposed_edf_scheduler::GlobalEDFScheduler,
+        ros2_executor_scheduler::ROS2ExecutorScheduler,
+    },
+    task::{
+        arrival::{derive_run_seed, randomize_offsets},
+        dag::DAG,
+        dag_creator::{create_dag_set_from_dir, dump_dag_to_yaml},
+        dag_generator::{generate_dag_set, DAGGeneratorConfig, GenerationMethod},
+        dag_set::scale_periods_to_utilization,
     },
-    task::{dag::DAG, dag_creator::create_dag_set_from_dir},
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum Algorithm {
+    #[value(name = "proposed_edf")]
+    ProposedEdf,
+    Llf,
+    Edzl,
+    Federated,
+    Rm,
+    #[value(name = "partitioned_edf")]
+    PartitionedEdf,
+    #[value(name = "partitioned_rm")]
+    PartitionedRm,
+    Greedy,
+    #[value(name = "ros2_executor")]
+    Ros2Executor,
+}
+
+impl Algorithm {
+    /// The non-preemptive algorithms have no priorities to raise, so they only support the protocols that do not.
+    fn supports_resource_protocol(self, resource_protocol: ResourceProtocol) -> bool {
+        match self {
+            Algorithm::Greedy | Algorithm::Ros2Executor => matches!(
+                resource_protocol,
+                ResourceProtocol::None | ResourceProtocol::Mrsp
+            ),
+            _ => true,
+        }
+    }
+}
+
 #[derive(Parser)]
+#[clap(subcommand_negates_reqs = true)]
 struct ArgParser {
+    #[clap(subcommand)]
+    command: Option<Command>,
     /// Path to DAGSet directory.
     #[clap(short = 'd', long = "dag_dir_path", default_value = "../autoware_dags")]
     dag_dir_path: String,
+    /// Path to execution time directory. The file names of relative `execution_time_file` paths in DAG YAML files are
+    /// looked up in it, or the paths are resolved against the directory of each DAG YAML file if omitted.
+    #[clap(short = 'e', long = "et_dir_path")]
+    et_dir_path: Option<String>,
     /// Number of processing cores.
     #[clap(short = 'c', long = "num_cores", required = true)]
-    num_cores: usize,
+    num_cores: Option<usize>,
+    /// Speed factor of each core, e.g., `1.0,1.0,0.5,0.5` for a big.LITTLE SoC. Must have `num_cores` entries.
+    /// Federated scheduling assumes identical cores and is skipped unless all speeds are 1.0.
+    #[clap(long = "core_speeds", value_delimiter = ',')]
+    core_speeds: Option<Vec<f64>>,
     /// Simulation duration.
     #[clap(short = 's', long = "sim_duration", required = true)]
-    sim_duration: i32,
+    sim_duration: Option<i32>,
     /// Number of simulation runs.
     #[clap(short = 'r', long = "num_runs", default_value = "1")]
     num_runs: i32,
+    /// Algorithms to simulate, e.g., `proposed_edf,llf,federated`. Each writes its logs under its own name.
+    #[clap(
+        long = "algorithms",
+        value_enum,
+        value_delimiter = ',',
+        default_value = "proposed_edf,rm,greedy"
+    )]
+    algorithms: Vec<Algorithm>,
     /// Path to output directory.
     #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
     output_dir_path: String,
+    /// Keep simulating after a deadline miss and report tardiness.
+    #[clap(long = "continue_after_deadline_miss")]
+    continue_after_deadline_miss: bool,
+    /// Base seed for execution time sampling. The seed of each run is derived from it and the run index.
+    #[clap(long = "seed")]
+    seed: Option<u64>,
+    /// Seed of a single run as recorded in its log, to regenerate that run. Replaces `--seed` and `-r`.
+    #[clap(long = "run_seed", conflicts_with_all = ["seed", "num_runs"])]
+    run_seed: Option<u64>,
+    /// Bin-packing heuristic for the partitioned EDF and RM.
+    #[clap(long = "partitioning_heuristic", value_enum, default_value = "ffd")]
+    partitioning_heuristic: BinPackingHeuristic,
+    /// Whether the partitioned EDF and RM map each node or each whole DAG to a core.
+    #[clap(long = "partitioning_unit", value_enum, default_value = "node")]
+    partitioning_unit: PartitioningUnit,
+    /// Only preempt running nodes at their preemption points (`npr_length` or `preemption_points`), lazily or eagerly.
+    #[clap(long = "limited_preemption", value_enum)]
+    limited_preemption: Option<PreemptionMode>,
+    /// Processor time charged on every allocation of a node to a core.
+    #[clap(long = "context_switch_overhead", default_value = "0")]
+    context_switch_overhead: i32,
+    /// Processor time charged when a preempted node resumes on a different core.
+    #[clap(long = "migration_overhead", default_value = "0")]
+    migration_overhead: i32,
+    /// Processor time charged when a preempted node resumes (cache-related preemption delay).
+    #[clap(long = "crpd", default_value = "0")]
+    cache_related_preemption_delay: i32,
+    /// Precedence between consecutive jobs of the same DAG when their executions overlap.
+    #[clap(
+        long = "intra_task_precedence",
+        value_enum,
+        default_value = "independent"
+    )]
+    intra_task_precedence: IntraTaskPrecedence,
+    /// When the `delay` of a link is applied between the completion of a node and the start of its successor.
+    #[clap(long = "communication_delay", value_enum, default_value = "always")]
+    communication_delay: CommunicationDelayMode,
+    /// Protocol for the resources of the critical sections (`cs.<resource>`) of nodes. `pip`, `pcp` and `mpcp` raise
+    /// priorities, so they are not supported by the non-preemptive `greedy` and `ros2_executor`.
+    #[clap(long = "resource_protocol", value_enum, default_value = "none")]
+    resource_protocol: ResourceProtocol,
+    /// Normalized utilizations (total utilization over the total core speed) to scale the periods to, e.g.,
+    /// `0.5,0.55,0.6`. Each run takes the one at its index modulo the number of utilizations, and logs it. To
+    /// regenerate a run with `--run_seed`, give its logged utilization alone.
+    #[clap(long = "normalized_utilizations", value_delimiter = ',')]
+    normalized_utilizations: Option<Vec<f64>>,
+    /// Release each DAG at a random offset in [0, period) instead of the `offset` in its YAML.
+    #[clap(long = "random_offsets")]
+    random_offsets: bool,
+    /// Number of threads of each ROS 2 executor, e.g., `1,1,2` for two single-threaded executors and a
+    /// multi-threaded executor with 2 threads. Nodes choose their executor with the `executor` parameter.
+    /// By default, a single multi-threaded executor has a thread on each core.
+    #[clap(long = "executor_threads", value_delimiter = ',')]
+    executor_threads: Option<Vec<usize>>,
+    /// Bound the response times of the sinks analytically for the proposed EDF and RM, and dump reports that compare
+    /// the bounds with the simulated worst response times. The analysis assumes identical cores.
+    #[clap(long = "response_time_analysis")]
+    response_time_analysis: bool,
+    /// Run the analytical schedulability tests on the DAG set of each run, and dump their verdicts next to the logs.
+    /// The tests assume identical cores.
+    #[clap(long = "schedulability_tests")]
+    schedulability_tests: bool,
+    /// Record the execution trace of each core and dump it as an SVG Gantt chart and a Chrome trace JSON.
+    #[clap(long = "trace", alias = "gantt_chart")]
+    trace: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the structural metrics of each DAG and its nodes instead of simulating.
+    Describe {
+        /// Path to DAGSet directory.
+        #[clap(short = 'd', long = "dag_dir_path", default_value = "../autoware_dags")]
+        dag_dir_path: String,
+        /// Path to execution time directory. Defaults to the directory of each DAG YAML file.
+        #[clap(short = 'e', long = "et_dir_path")]
+        et_dir_path: Option<String>,
+        /// Seed for execution time sampling.
+        #[clap(long = "seed", default_value = "0")]
+        seed: u64,
+    },
+    /// Generate random DAGs and write them as DAG YAML files, which can be simulated with `-d`.
+    Generate {
+        /// Path to the directory to write the DAG YAML files to.
+        #[clap(
+            short = 'o',
+            long = "output_dir_path",
+            default_value = "../generated_dags"
+        )]
+        output_dir_path: String,
+        /// Number of DAGs.
+        #[clap(long = "num_dags", default_value = "10")]
+        num_dags: usize,
+        #[clap(long = "method", value_enum, default_value = "layered")]
+        method: GenerationMethod,
+        /// Number of nodes of each DAG.
+        #[clap(long = "num_nodes", default_value = "10")]
+        num_nodes: usize,
+        /// Probability of each link for the Erdős–Rényi and layered methods.
+        #[clap(long = "edge_probability", default_value = "0.3")]
+        edge_probability: f64,
+        /// Number of layers, or the maximum nesting depth of the fork-joins.
+        #[clap(long = "depth", default_value = "4")]
+        depth: usize,
+        /// Maximum number of branches of each fork-join.
+        #[clap(long = "max_branches", default_value = "3")]
+        max_branches: usize,
+        #[clap(long = "min_execution_time", default_value = "1")]
+        min_execution_time: i32,
+        #[clap(long = "max_execution_time", default_value = "100")]
+        max_execution_time: i32,
+        /// Period and relative deadline of each DAG.
+        #[clap(long = "period", default_value = "1000")]
+        period: i32,
+        #[clap(long = "seed", default_value = "0")]
+        seed: u64,
+    },
+}
+
+fn describe(dag_dir_path: &str, et_dir_path: Option<&str>, seed: u64) {
+    let dag_set = create_dag_set_from_dir(dag_dir_path, et_dir_path, seed);
+    for dag in dag_set.iter() {
+        println!("DAG {}", dag.get_dag_param("dag_id"));
+        println!("  utilization: {:.3}", dag.get_utilization());
+        println!("  volume: {}", dag.get_volume());
+        println!("  critical path length: {}", dag.get_critical_path_length());
+        println!(
+            "  critical path length with delays: {}",
+            dag.get_critical_path_length_with_delays()
+        );
+        println!("  depth: {}", dag.get_depth());
+        println!("  width: {}", dag.get_width());
+        println!(
+            "  parallelism profile (time, running nodes): {:?}",
+            dag.get_parallelism_profile()
+        );
+        println!("  nodes (id: execution time, EST, LST, slack):");
+        let earliest_start_times = dag.get_earliest_start_times();
+        let latest_start_times = dag.get_latest_start_times();
+        let slacks = dag.get_slacks();
+        for node_i in dag.node_indices() {
+            let i = node_i.index();
+            println!(
+                "    {}: {}, {}, {}, {}",
+                dag[node_i].id,
+                dag[node_i].get_value("execution_time"),
+                earliest_start_times[i],
+                latest_start_times[i],
+                slacks[i]
+            );
+        }
+    }
 }
 
 fn main() {
     let arg: ArgParser = ArgParser::parse();
+    match &arg.command {
+        Some(Command::Describe {
+            dag_dir_path,
+            et_dir_path,
+            seed,
+        }) => {
+            describe(dag_dir_path, et_dir_path.as_deref(), *seed);
+            return;
+        }
+        Some(Command::Generate {
+            output_dir_path,
+            num_dags,
+            method,
+            num_nodes,
+            edge_probability,
+            depth,
+            max_branches,
+            min_execution_time,
+            max_execution_time,
+            period,
+            seed,
+        }) => {
+            let config = DAGGeneratorConfig {
+                method: *method,
+                num_nodes: *num_nodes,
+                edge_probability: *edge_probability,
+                depth: *depth,
+                max_branches: *max_branches,
+                min_execution_time: *min_execution_time,
+                max_execution_time: *max_execution_time,
+                period: *period,
+            };
+            // Zero-padded so that the loader, which sorts the file names, keeps the order of the DAGs.
+            let width = num_dags.to_string().len();
+            for (dag_id, dag) in generate_dag_set(&config, *num_dags, *seed)
+                .iter()
+                .enumerate()
+            {
+                dump_dag_to_yaml(
+                    dag,
+                    &format!("{}/dag_{:0width$}.yaml", output_dir_path, dag_id),
+                );
+            }
+            return;
+        }
+        None => {}
+    }
+    // Both are required unless a subcommand is given.
+    let num_cores = arg.num_cores.unwrap();
+    let sim_duration = arg.sim_duration.unwrap();
+    let base_seed = arg.seed.unwrap_or_else(rand::random);
+    let overheads = Overheads {
+        context_switch: arg.context_switch_overhead,
+        migration: arg.migration_overhead,
+        cache_related_preemption_delay: arg.cache_related_preemption_delay,
+    };
+    let core_speeds = arg
+        .core_speeds
+        .clone()
+        .unwrap_or_else(|| vec![1.0; num_cores]);
+    if core_speeds.len() != num_cores {
+        ArgParser::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "{} core speeds are given for {} cores.",
+                    core_speeds.len(),
+                    num_cores
+                ),
+            )
+            .exit();
+    }
+    if let Some(algorithm) = arg
+        .algorithms
+        .iter()
+        .find(|algorithm| !algorithm.supports_resource_protocol(arg.resource_protocol))
+    {
+        ArgParser::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "`--resource_protocol {}` is not supported by {}.",
+                    arg.resource_protocol
+                        .to_possible_value()
+                        .unwrap()
+                        .get_name(),
+                    algorithm.to_possible_value().unwrap().get_name()
+                ),
+            )
+            .exit();
+    }
+    let is_homogeneous = core_speeds.iter().all(|&speed| speed == 1.0);
+    if arg.response_time_analysis && !is_homogeneous {
+        println!("Response-time analysis is skipped on heterogeneous cores.");
+    }
+    let is_response_time_analyzed = arg.response_time_analysis && is_homogeneous;
+    if arg.schedulability_tests && !is_homogeneous {
+        println!("Schedulability tests are skipped on heterogeneous cores.");
+    }
+    let are_schedulability_tests_run = arg.schedulability_tests && is_homogeneous;
+    if arg.algorithms.contains(&Algorithm::Federated) && !is_homogeneous {
+        println!("Federated scheduling is skipped on heterogeneous cores.");
+    }
+    let algorithms = arg
+        .algorithms
+        .iter()
+        .copied()
+        .filter(|&algorithm| is_homogeneous || algorithm != Algorithm::Federated)
+        .collect::<Vec<_>>();
+    let new_processor = || HeterogeneousProcessor::new_with_speeds(&core_speeds, overheads);
+    let executor_threads = arg
+        .executor_threads
+        .clone()
+        .unwrap_or_else(|| vec![num_cores]);
+    if algorithms.contains(&Algorithm::Ros2Executor) {
+        // The executors and affinities do not depend on the seed, so the DAG set is checked once before the runs.
+        let dag_set =
+            create_dag_set_from_dir(&arg.dag_dir_path, arg.et_dir_path.as_deref(), base_seed);
+        if let Err(err) = ROS2ExecutorScheduler::try_new_with_executors(
+            &dag_set,
+            &new_processor(),
+            &executor_threads,
+        ) {
+            ArgParser::command()
+                .error(ErrorKind::ArgumentConflict, err.to_string())
+                .exit();
+        }
+    }
+    let preemptive_type = |key: &str| match arg.limited_preemption {
+        Some(mode) => PreemptiveType::LimitedPreemptive {
+            key: key.to_string(),
+            mode,
+        },
+        None => PreemptiveType::Preemptive {
+            key: key.to_string(),
+        },
+    };
+
+    // (run index, seed)
+    let runs = match arg.run_seed {
+        Some(run_seed) => vec![(0, run_seed)],
+        None => (0..arg.num_runs as usize)
+            .map(|run_i| (run_i, derive_run_seed(base_seed, run_i as u64)))
+            .collect(),
+    };
 
-    (0..arg.num_runs).into_par_iter().for_each(|_| {
-        let dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
+    runs.into_par_iter().for_each(|(run_i, seed)| {
+        // Taken by the run index so that each bin of the acceptance-ratio plot gets the same number of runs.
+        let normalized_utilization =
+            arg.normalized_utilizations
+                .as_ref()
+                .map(|normalized_utilizations| {
+                    normalized_utilizations[run_i % normalized_utilizations.len()]
+                });
+        let config = SchedulerConfig {
+            continue_after_deadline_miss: arg.continue_after_deadline_miss,
+            seed,
+            record_trace: arg.trace,
+            intra_task_precedence: arg.intra_task_precedence,
+            communication_delay: arg.communication_delay,
+            resource_protocol: arg.resource_protocol,
+            normalized_utilization,
+        };
+        // Counted as a deadline miss of the algorithm, so that its acceptance ratio does not lose the run.
+        let dump_unschedulable = |alg_name: &str, total_utilization: f64, reason: &str| {
+            dump_unschedulable_run(
+                &arg.output_dir_path,
+                alg_name,
+                seed,
+                normalized_utilization,
+                total_utilization,
+                num_cores,
+                reason,
+            )
+        };
+        let mut dag_set =
+            create_dag_set_from_dir(&arg.dag_dir_path, arg.et_dir_path.as_deref(), seed);
+        if let Some(normalized_utilization) = normalized_utilization {
+            let total_utilization = normalized_utilization * core_speeds.iter().sum::<f64>();
+            if let Err(err) =
+                scale_periods_to_utilization(&mut dag_set, total_utilization, sim_duration, seed)
+            {
+                println!("Scaling the periods failed (seed: {}): {}", seed, err);
+                for algorithm in algorithms.iter() {
+                    dump_unschedulable(
+                        algorithm.to_possible_value().unwrap().get_name(),
+                        total_utilization,
+                        &err.to_string(),
+                    );
+                }
+                return;
+            }
+        }
+        if arg.random_offsets {
+            randomize_offsets(&mut dag_set, seed);
+        }
+        let total_utilization = dag_set
+            .iter()
+            .map(|dag| dag.get_utilization() as f64)
+            .sum::<f64>();
+
+        if are_schedulability_tests_run {
+            for test in SchedulabilityTest::value_variants() {
+                dump_schedulability_test_result(
+                    &arg.output_dir_path,
+                    test.to_possible_value().unwrap().get_name(),
+                    seed,
+                    &dag_set,
+                    num_cores,
+                    &test.run(&dag_set, num_cores),
+                );
+            }
+        }
 
         // The reason why the all algorithms are executed in the same main function is
         // to use the same DAGSet that is randomly generated.
 
-        // Proposed EDF
-        let processor = HomogeneousProcessor::new(arg.num_cores);
-        let mut scheduler = GlobalEDFScheduler::new(&dag_set.clone(), &processor);
-        scheduler.schedule(
-            PreemptiveType::Preemptive {
-                key: "ref_absolute_deadline".to_string(),
-            },
-            arg.sim_duration,
-        );
-        scheduler.dump_log(&arg.output_dir_path, "proposed_edf", false);
-
-        // RM
-        let processor = HomogeneousProcessor::new(arg.num_cores);
+        let is_selected = |algorithm| algorithms.contains(&algorithm);
         let mut rm_dag_set = dag_set.clone();
         for dag in rm_dag_set.iter_mut() {
             let dag_period = dag.get_dag_param("period");
@@ -59,26 +479,152 @@ fn main() {
                 node.params.insert("priority".to_string(), dag_period);
             }
         }
-        let mut scheduler = FixedPriorityScheduler::new(&rm_dag_set, &processor);
-        scheduler.schedule(
-            PreemptiveType::Preemptive {
-                key: "priority".to_string(),
-            },
-            arg.sim_duration,
-        );
-        scheduler.dump_log(&arg.output_dir_path, "rm", false);
+
+        // Proposed EDF
+        if is_selected(Algorithm::ProposedEdf) {
+            let processor = new_processor();
+            let mut scheduler = GlobalEDFScheduler::new(&dag_set.clone(), &processor);
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(preemptive_type("ref_absolute_deadline"), sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "proposed_edf", false);
+            if is_response_time_analyzed {
+                let bounds = analyze_response_times(&dag_set, num_cores, AnalyzedPolicy::Edf);
+                scheduler.get_log().dump_response_time_report(
+                    &arg.output_dir_path,
+                    "proposed_edf",
+                    &bounds,
+                );
+            }
+        }
+
+        // LLF
+        if is_selected(Algorithm::Llf) {
+            let processor = new_processor();
+            let mut scheduler = GlobalLLFScheduler::new(&dag_set, &processor);
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(preemptive_type("latest_start_time"), sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "llf", false);
+        }
+
+        // EDZL
+        if is_selected(Algorithm::Edzl) {
+            let processor = new_processor();
+            let mut scheduler = EDZLScheduler::new(&dag_set, &processor);
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(preemptive_type("edzl_priority"), sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "edzl", false);
+        }
+
+        // Federated
+        if is_selected(Algorithm::Federated) {
+            let processor = HomogeneousProcessor::new_with_overheads(num_cores, overheads);
+            match FederatedScheduler::try_new(&dag_set, &processor) {
+                Ok(mut scheduler) => {
+                    *scheduler.get_config_mut() = config.clone();
+                    scheduler.schedule(preemptive_type("ref_absolute_deadline"), sim_duration);
+                    scheduler.dump_log(&arg.output_dir_path, "federated", false);
+                }
+                Err(err) => {
+                    println!(
+                        "Federated scheduling is not applicable (seed: {}): {}",
+                        seed, err
+                    );
+                    dump_unschedulable("federated", total_utilization, &err.to_string());
+                }
+            }
+        }
+
+        // RM
+        if is_selected(Algorithm::Rm) {
+            let processor = new_processor();
+            let mut scheduler = FixedPriorityScheduler::new(&rm_dag_set, &processor);
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(preemptive_type("priority"), sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "rm", false);
+            if is_response_time_analyzed {
+                let bounds = analyze_response_times(&rm_dag_set, num_cores, AnalyzedPolicy::Rm);
+                scheduler
+                    .get_log()
+                    .dump_response_time_report(&arg.output_dir_path, "rm", &bounds);
+            }
+        }
+
+        // Partitioned EDF
+        if is_selected(Algorithm::PartitionedEdf) {
+            let mut partitioned_edf_dag_set = dag_set.clone();
+            match partition_dag_set_with_speeds(
+                &mut partitioned_edf_dag_set,
+                &core_speeds,
+                arg.partitioning_heuristic,
+                arg.partitioning_unit,
+            ) {
+                Ok(_) => {
+                    let processor = new_processor();
+                    let mut scheduler =
+                        GlobalEDFScheduler::new(&partitioned_edf_dag_set, &processor);
+                    *scheduler.get_config_mut() = config.clone();
+                    scheduler.schedule(preemptive_type("ref_absolute_deadline"), sim_duration);
+                    scheduler.dump_log(&arg.output_dir_path, "partitioned_edf", false);
+                }
+                Err(err) => {
+                    println!("Partitioning failed (seed: {}): {}", seed, err);
+                    dump_unschedulable("partitioned_edf", total_utilization, &err.to_string());
+                }
+            }
+        }
+
+        // Partitioned RM
+        if is_selected(Algorithm::PartitionedRm) {
+            let mut partitioned_rm_dag_set = rm_dag_set.clone();
+            match partition_dag_set_with_speeds(
+                &mut partitioned_rm_dag_set,
+                &core_speeds,
+                arg.partitioning_heuristic,
+                arg.partitioning_unit,
+            ) {
+                Ok(_) => {
+                    let processor = new_processor();
+                    let mut scheduler =
+                        FixedPriorityScheduler::new(&partitioned_rm_dag_set, &processor);
+                    *scheduler.get_config_mut() = config.clone();
+                    scheduler.schedule(preemptive_type("priority"), sim_duration);
+                    scheduler.dump_log(&arg.output_dir_path, "partitioned_rm", false);
+                }
+                Err(err) => {
+                    println!("Partitioning failed (seed: {}): {}", seed, err);
+                    dump_unschedulable("partitioned_rm", total_utilization, &err.to_string());
+                }
+            }
+        }
 
         // Greedy
-        let processor = HomogeneousProcessor::new(arg.num_cores);
-        let mut greedy_dag_set = dag_set.clone();
-        let uniform_priority = 0;
-        for dag in greedy_dag_set.iter_mut() {
-            for node in dag.node_weights_mut() {
-                node.params.insert("priority".to_string(), uniform_priority);
+        if is_selected(Algorithm::Greedy) {
+            let processor = new_processor();
+            let mut greedy_dag_set = dag_set.clone();
+            let uniform_priority = 0;
+            for dag in greedy_dag_set.iter_mut() {
+                for node in dag.node_weights_mut() {
+                    node.params.insert("priority".to_string(), uniform_priority);
+                }
             }
+            let mut scheduler = FixedPriorityScheduler::new(&greedy_dag_set, &processor);
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(PreemptiveType::NonPreemptive, sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "greedy", false);
+        }
+
+        // ROS 2 executors
+        if is_selected(Algorithm::Ros2Executor) {
+            let processor = new_processor();
+            let mut scheduler = ROS2ExecutorScheduler::try_new_with_executors(
+                &dag_set,
+                &processor,
+                &executor_threads,
+            )
+            .expect("The executors are checked before the runs.");
+            *scheduler.get_config_mut() = config.clone();
+            scheduler.schedule(PreemptiveType::NonPreemptive, sim_duration);
+            scheduler.dump_log(&arg.output_dir_path, "ros2_executor", false);
         }
-        let mut scheduler = FixedPriorityScheduler::new(&greedy_dag_set, &processor);
-        scheduler.schedule(PreemptiveType::NonPreemptive, arg.sim_duration);
-        scheduler.dump_log(&arg.output_dir_path, "greedy", false);
     });
 }
@@ -1,3 +1,4 @@
 pub mod core;
+pub mod heterogeneous;
 pub mod homogeneous;
 pub mod processor_interface;
@@ -14,13 +14,70 @@ pub enum ProcessResult {
     Done(Node),
 }
 
+/// Costs charged as processor time before a node starts or resumes on a core.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Overheads {
+    /// Charged on every allocation.
+    pub context_switch: i32,
+    /// Charged when a preempted node resumes on a different core.
+    pub migration: i32,
+    /// Cache-related preemption delay. Charged when a preempted node resumes.
+    pub cache_related_preemption_delay: i32,
+}
+
+impl Overheads {
+    /// `last_core_id` is written to a node when it is preempted.
+    pub fn get_allocation_overhead(&self, core_id: usize, node: &Node) -> i32 {
+        let mut overhead = self.context_switch;
+        if node.params.contains_key("is_preempted") {
+            overhead += self.cache_related_preemption_delay;
+        }
+        if let Some(&last_core_id) = node.params.get("last_core_id") {
+            if last_core_id as usize != core_id {
+                overhead += self.migration;
+            }
+        }
+        overhead
+    }
+}
+
+/// `partial_work` of a preempted node is the work it did beyond its last whole unit, in units of 1 / `WORK_SCALE`.
+/// It counts as done on the next allocation, so a node on a slow core does not lose work at every preemption.
+pub const WORK_SCALE: i32 = 1_000_000;
+/// Absorbs the rounding error of scaling work by the speed.
+const WORK_TOLERANCE: f64 = 1e-9;
+
+fn get_partial_work(node: &Node) -> f64 {
+    node.get_value_or("partial_work", 0) as f64 / WORK_SCALE as f64
+}
+
+/// The first preemption point after `executed_time`: the next multiple of `npr_length` or the next point in
+/// `preemption_point.<i>`, whichever comes first.
+fn get_next_preemption_point(node: &Node, executed_time: i32) -> Option<i32> {
+    let npr_end = node
+        .params
+        .get("npr_length")
+        .map(|&npr_length| executed_time + npr_length - executed_time % npr_length);
+    let preemption_point = node
+        .get_preemption_points()
+        .into_iter()
+        .find(|&preemption_point| preemption_point > executed_time);
+    npr_end.into_iter().chain(preemption_point).min()
+}
+
 #[derive(Clone, CopyGetters, Getters, Debug)]
 pub struct Core {
     #[get_copy = "pub with_prefix"]
     pub is_idle: bool,
     #[get = "pub with_prefix"]
     pub processing_node: Option<Node>,
+    /// Remaining time of the processing node on this core, i.e., its remaining work scaled by `speed`.
     pub remain_proc_time: i32,
+    /// Work done per unit of time. `execution_time` of a node is its work at speed 1.0.
+    pub speed: f64,
+    /// Overhead to be consumed before the processing node makes progress.
+    pub remain_overhead: i32,
+    pub total_overhead_time: i32,
 }
 
 impl Default for Core {
@@ -29,11 +86,59 @@ impl Default for Core {
             is_idle: true,
             processing_node: None,
             remain_proc_time: 0,
+            speed: 1.0,
+            remain_overhead: 0,
+            total_overhead_time: 0,
         }
     }
 }
 
 impl Core {
+    pub fn new(speed: f64) -> Self {
+        if speed <= 0.0 {
+            panic!(
+                "The speed of a core must be positive, but {} is given",
+                speed
+            );
+        }
+        Self {
+            speed,
+            ..Default::default()
+        }
+    }
+
+    /// Time this core takes to execute `work` of the node since the allocation, rounded up. The partial work that the
+    /// node carries counts as done.
+    fn get_proc_time(&self, node: &Node, work: i32) -> i32 {
+        ((work as f64 - get_partial_work(node)) / self.speed - WORK_TOLERANCE)
+            .ceil()
+            .max(0.0) as i32
+    }
+
+    /// Exact work done by this core in `elapsed_time` since the allocation, including the carried partial work.
+    fn get_exact_work_done(&self, node: &Node, elapsed_time: i32) -> f64 {
+        elapsed_time as f64 * self.speed + get_partial_work(node)
+    }
+
+    /// Work done by this core in `elapsed_time` since the allocation, rounded down.
+    fn get_work_done(&self, node: &Node, elapsed_time: i32) -> i32 {
+        ((self.get_exact_work_done(node, elapsed_time) + WORK_TOLERANCE).floor() as i32)
+            .min(node.get_value("execution_time"))
+    }
+
+    /// Time the processing node has made progress on this core since the allocation, excluding overhead.
+    fn get_elapsed_time(&self, node: &Node) -> i32 {
+        self.get_proc_time(node, node.get_value("execution_time")) - self.remain_proc_time
+    }
+
+    /// Remaining work of the processing node in whole units. The partial work is carried by `preempt`.
+    pub fn get_remain_work(&self) -> i32 {
+        let Some(node) = &self.processing_node else {
+            return 0;
+        };
+        node.get_value("execution_time") - self.get_work_done(node, self.get_elapsed_time(node))
+    }
+
     pub fn allocate(&mut self, node: &Node) {
         if !self.is_idle {
             panic!("The node is already allocated");
@@ -42,18 +147,39 @@ impl Core {
         self.is_idle = false;
         self.processing_node = Some(node.clone());
         if let Some(exec_time) = node.params.get("execution_time") {
-            self.remain_proc_time = *exec_time;
+            self.remain_proc_time = self.get_proc_time(node, *exec_time);
         } else {
             panic!("Node {} does not have execution_time", node.id);
         }
+        self.remain_overhead = 0;
+    }
+
+    pub fn charge_overhead(&mut self, overhead: i32) {
+        self.remain_overhead += overhead;
     }
 
-    pub fn process(&mut self) -> ProcessResult {
+    /// Time until the processing node finishes, including the remaining overhead.
+    pub fn get_remain_busy_time(&self) -> i32 {
+        self.remain_overhead + self.remain_proc_time
+    }
+
+    pub fn process(&mut self, time: i32) -> ProcessResult {
         if self.is_idle {
             return Idle;
         }
 
-        self.remain_proc_time -= 1;
+        if time > self.get_remain_busy_time() {
+            panic!(
+                "Processing time {} exceeds the remaining processing time {}",
+                time,
+                self.get_remain_busy_time()
+            );
+        }
+
+        let overhead_time = time.min(self.remain_overhead);
+        self.remain_overhead -= overhead_time;
+        self.total_overhead_time += overhead_time;
+        self.remain_proc_time -= time - overhead_time;
         if self.remain_proc_time == 0 {
             self.is_idle = true;
             let finish_node_data = self.processing_node.take().unwrap();
@@ -63,18 +189,89 @@ impl Core {
         InProgress
     }
 
+    /// Work the processing node has executed so far, including before it was preempted.
+    pub fn get_executed_time(&self) -> i32 {
+        match &self.processing_node {
+            Some(node) => {
+                node.params.get("executed_time").copied().unwrap_or(0)
+                    + node.get_value("execution_time")
+                    - self.get_remain_work()
+            }
+            None => 0,
+        }
+    }
+
+    /// A node may be preempted before it starts, whenever its executed time is a multiple of `npr_length`, and at the
+    /// executed times in its `preemption_point.<i>`. A node without either is non-preemptive once started.
+    /// A core faster than 1.0 may do more than one unit of work per time unit, so it also stops at the first time unit
+    /// that passes a preemption point.
+    pub fn is_at_preemption_point(&self) -> bool {
+        let Some(node) = &self.processing_node else {
+            return false;
+        };
+        let executed_time = self.get_executed_time();
+        if executed_time == 0 {
+            return true;
+        }
+        let elapsed_time = self.get_elapsed_time(node);
+        let prev_executed_time = if elapsed_time == 0 {
+            executed_time - 1
+        } else {
+            executed_time - self.get_work_done(node, elapsed_time)
+                + self.get_work_done(node, elapsed_time - 1)
+        };
+        get_next_preemption_point(node, prev_executed_time)
+            .is_some_and(|preemption_point| preemption_point <= executed_time)
+    }
+
+    pub fn get_time_to_next_preemption_point(&self) -> Option<i32> {
+        let node = self.processing_node.as_ref()?;
+        self.get_time_to_executed_time(get_next_preemption_point(node, self.get_executed_time())?)
+    }
+
+    /// Time until the executed time of the processing node reaches `executed_time`, including the remaining overhead.
+    pub fn get_time_to_executed_time(&self, executed_time: i32) -> Option<i32> {
+        let node = self.processing_node.as_ref()?;
+        // Work to be done on this core since the allocation
+        let target_work = self.get_work_done(node, self.get_elapsed_time(node)) + executed_time
+            - self.get_executed_time();
+        Some(
+            self.remain_overhead + self.get_proc_time(node, target_work)
+                - self.get_elapsed_time(node),
+        )
+    }
+
     pub fn preempt(&mut self) -> Node {
         if self.is_idle {
             panic!("Although the core is idle, preempt is called");
         }
 
+        let executed_time = self.get_executed_time();
+        let remain_work = self.get_remain_work();
+        let partial_work = match &self.processing_node {
+            Some(node) if remain_work > 0 => {
+                let elapsed_time = self.get_elapsed_time(node);
+                let exact_work_done = self.get_exact_work_done(node, elapsed_time);
+                let partial_work = exact_work_done - self.get_work_done(node, elapsed_time) as f64;
+                ((partial_work.max(0.0) * WORK_SCALE as f64).round() as i32).min(WORK_SCALE - 1)
+            }
+            _ => 0,
+        };
         let mut node_data = self.processing_node.take().unwrap();
         node_data
             .params
-            .insert("execution_time".to_string(), self.remain_proc_time);
+            .insert("execution_time".to_string(), remain_work);
+        node_data
+            .params
+            .insert("executed_time".to_string(), executed_time);
+        node_data
+            .params
+            .insert("partial_work".to_string(), partial_work);
         node_data.params.insert("is_preempted".to_string(), 1);
         self.is_idle = true;
         self.remain_proc_time = 0;
+        // Overhead that has not been consumed yet is discarded.
+        self.remain_overhead = 0;
         node_data
     }
 }
@@ -87,7 +284,7 @@ mod tests_core {
     fn create_node(key: &str, value: Option<i32>) -> Node {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value.unwrap_or_default());
-        Node { id: 0, params }
+        Node::new(0, params)
     }
 
     #[test]
@@ -122,14 +319,34 @@ mod tests_core {
         const DUMMY_ET: i32 = 10;
         let mut core = Core::default();
         core.allocate(&create_node("execution_time", Some(DUMMY_ET)));
-        assert_eq!(core.process(), InProgress);
+        assert_eq!(core.process(1), InProgress);
         assert_eq!(core.remain_proc_time, DUMMY_ET - 1);
     }
 
+    #[test]
+    fn test_process_multiple_units() {
+        const DUMMY_ET: i32 = 10;
+        let dummy_node = create_node("execution_time", Some(DUMMY_ET));
+        let mut core = Core::default();
+        core.allocate(&dummy_node);
+        assert_eq!(core.process(4), InProgress);
+        assert_eq!(core.remain_proc_time, DUMMY_ET - 4);
+        assert_eq!(core.process(DUMMY_ET - 4), Done(dummy_node));
+        assert!(core.is_idle);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_process_exceeds_remain_proc_time() {
+        let mut core = Core::default();
+        core.allocate(&create_node("execution_time", Some(2)));
+        core.process(3);
+    }
+
     #[test]
     fn test_process_idle() {
         let mut core = Core::default();
-        assert_eq!(core.process(), Idle);
+        assert_eq!(core.process(1), Idle);
     }
 
     #[test]
@@ -137,7 +354,7 @@ mod tests_core {
         let dummy_node = create_node("execution_time", Some(1));
         let mut core = Core::default();
         core.allocate(&dummy_node);
-        assert_eq!(core.process(), Done(dummy_node));
+        assert_eq!(core.process(1), Done(dummy_node));
         assert!(core.is_idle);
         assert_eq!(core.processing_node, None);
         assert_eq!(core.remain_proc_time, 0);
@@ -150,13 +367,103 @@ mod tests_core {
         let mut core = Core::default();
 
         core.allocate(&dummy_node);
-        core.process();
+        core.process(1);
 
         let preempted_node = core.preempt();
         assert_eq!(preempted_node.get_value("execution_time"), DUMMY_ET - 1);
         assert_eq!(preempted_node.get_value("is_preempted"), 1);
+        assert_eq!(preempted_node.get_value("executed_time"), 1);
         assert!(core.is_idle);
         assert_eq!(core.processing_node, None);
         assert_eq!(core.remain_proc_time, 0);
     }
+
+    #[test]
+    fn test_preemption_points() {
+        let mut node = create_node("execution_time", Some(5));
+        node.params.insert("npr_length".to_string(), 2);
+        let mut core = Core::default();
+
+        core.allocate(&node);
+        assert!(core.is_at_preemption_point());
+        core.process(1);
+        assert!(!core.is_at_preemption_point());
+        assert_eq!(core.get_time_to_next_preemption_point(), Some(1));
+        core.process(1);
+        assert!(core.is_at_preemption_point());
+
+        // The progress before preemption is kept.
+        let preempted_node = core.preempt();
+        core.allocate(&preempted_node);
+        core.process(1);
+        assert_eq!(core.get_executed_time(), 3);
+        assert_eq!(core.get_time_to_next_preemption_point(), Some(1));
+    }
+
+    #[test]
+    fn test_explicit_preemption_points() {
+        let mut node = create_node("execution_time", Some(6));
+        node.params.insert("preemption_point.0".to_string(), 1);
+        node.params.insert("preemption_point.1".to_string(), 4);
+        let mut core = Core::default();
+
+        core.allocate(&node);
+        assert_eq!(core.get_time_to_next_preemption_point(), Some(1));
+        core.process(1);
+        assert!(core.is_at_preemption_point());
+        assert_eq!(core.get_time_to_next_preemption_point(), Some(3));
+        core.process(2);
+        assert!(!core.is_at_preemption_point());
+        core.process(1);
+        assert!(core.is_at_preemption_point());
+        assert_eq!(core.get_time_to_next_preemption_point(), None);
+
+        // The points are combined with the multiples of `npr_length`.
+        node.params.insert("npr_length".to_string(), 3);
+        let mut core = Core::default();
+        core.allocate(&node);
+        core.process(1);
+        assert!(core.is_at_preemption_point());
+        assert_eq!(core.get_time_to_next_preemption_point(), Some(2));
+    }
+
+    #[test]
+    fn test_non_preemptive_node() {
+        let mut core = Core::default();
+        core.allocate(&create_node("execution_time", Some(5)));
+        assert!(core.is_at_preemption_point());
+        core.process(1);
+        assert!(!core.is_at_preemption_point());
+        assert_eq!(core.get_time_to_next_preemption_point(), None);
+    }
+
+    #[test]
+    fn test_process_with_overhead() {
+        let dummy_node = create_node("execution_time", Some(3));
+        let mut core = Core::default();
+        core.allocate(&dummy_node);
+        core.charge_overhead(2);
+        assert_eq!(core.get_remain_busy_time(), 5);
+
+        assert_eq!(core.process(3), InProgress);
+        assert_eq!(core.get_executed_time(), 1);
+        assert_eq!(core.total_overhead_time, 2);
+        assert_eq!(core.process(2), Done(dummy_node));
+    }
+
+    #[test]
+    fn test_get_allocation_overhead() {
+        let overheads = Overheads {
+            context_switch: 1,
+            migration: 10,
+            cache_related_preemption_delay: 100,
+        };
+        let mut node = create_node("execution_time", Some(3));
+        assert_eq!(overheads.get_allocation_overhead(0, &node), 1);
+
+        node.params.insert("is_preempted".to_string(), 1);
+        node.params.insert("last_core_id".to_string(), 0);
+        assert_eq!(overheads.get_allocation_overhead(0, &node), 101);
+        assert_eq!(overheads.get_allocation_overhead(1, &node), 111);
+    }
 }
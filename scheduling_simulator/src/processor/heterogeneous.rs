@@ -0,0 +1,234 @@
+//! Heterogeneous processor module, e.g., big.LITTLE. Each core has a speed factor, and the remaining work of a node is
+//! scaled by the speed of the core it runs on. Idle cores are assigned from the fastest one.
+
+use super::core::{Core, Overheads, ProcessResult};
+use crate::{processor::processor_interface::Processor, task::dag::Node};
+
+#[derive(Clone, Debug)]
+pub struct HeterogeneousProcessor {
+    pub cores: Vec<Core>,
+    pub overheads: Overheads,
+}
+
+impl HeterogeneousProcessor {
+    pub fn new_with_speeds(speeds: &[f64], overheads: Overheads) -> Self {
+        Self {
+            cores: speeds.iter().map(|&speed| Core::new(speed)).collect(),
+            overheads,
+        }
+    }
+
+    /// Identical cores at speed 1.0. See `HomogeneousProcessor`.
+    pub fn new_with_overheads(num_cores: usize, overheads: Overheads) -> Self {
+        Self::new_with_speeds(&vec![1.0; num_cores], overheads)
+    }
+}
+
+impl Processor for HeterogeneousProcessor {
+    fn new(num_cores: usize) -> Self {
+        Self::new_with_overheads(num_cores, Default::default())
+    }
+
+    fn allocate(&mut self, core_id: usize, node: &Node) {
+        let core = &mut self.cores[core_id];
+        core.allocate(node);
+        core.charge_overhead(self.overheads.get_allocation_overhead(core_id, node));
+    }
+
+    fn charge_overhead(&mut self, core_id: usize, overhead: i32) {
+        self.cores[core_id].charge_overhead(overhead);
+    }
+
+    fn process(&mut self, time: i32) -> Vec<ProcessResult> {
+        self.cores
+            .iter_mut()
+            .enumerate()
+            .map(|(core_i, core)| match core.process(time) {
+                ProcessResult::Done(mut node) => {
+                    node.params
+                        .insert("last_core_id".to_string(), core_i as i32);
+                    ProcessResult::Done(node)
+                }
+                result => result,
+            })
+            .collect()
+    }
+
+    fn get_num_cores(&self) -> usize {
+        self.cores.len()
+    }
+
+    fn get_processing_node(&self, core_id: usize) -> Option<&Node> {
+        self.cores[core_id].processing_node.as_ref()
+    }
+
+    fn get_num_idle_cores(&self) -> usize {
+        self.cores.iter().filter(|core| core.is_idle).count()
+    }
+
+    /// The fastest idle core, or the one with the smallest index among the fastest.
+    fn get_idle_core_i(&self, node: &Node) -> Option<usize> {
+        self.cores
+            .iter()
+            .enumerate()
+            .filter(|&(i, core)| core.is_idle && self.is_allowed_core(i, node))
+            .fold(
+                None,
+                |fastest: Option<(usize, f64)>, (i, core)| match fastest {
+                    Some((_, speed)) if speed >= core.speed => fastest,
+                    _ => Some((i, core.speed)),
+                },
+            )
+            .map(|(i, _)| i)
+    }
+
+    fn preempt(&mut self, core_id: usize) -> Node {
+        let mut node = self.cores[core_id].preempt();
+        node.params
+            .insert("last_core_id".to_string(), core_id as i32);
+        node
+    }
+
+    fn get_max_and_index(&self, key: &str, node: &Node) -> Option<(i32, usize)> {
+        self.cores
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| self.is_allowed_core(i, node))
+            .filter_map(|(i, core)| {
+                let node_data = core.processing_node.as_ref()?;
+                Some((node_data.get_value(key), i))
+            })
+            .max_by_key(|&(value, _)| value)
+    }
+
+    fn get_min_remain_proc_time(&self) -> Option<i32> {
+        self.cores
+            .iter()
+            .filter(|core| !core.is_idle)
+            .map(|core| core.get_remain_busy_time())
+            .min()
+    }
+
+    fn get_total_overhead_times(&self) -> Vec<i32> {
+        self.cores
+            .iter()
+            .map(|core| core.total_overhead_time)
+            .collect()
+    }
+
+    fn get_core_speeds(&self) -> Vec<f64> {
+        self.cores.iter().map(|core| core.speed).collect()
+    }
+
+    fn is_at_preemption_point(&self, core_id: usize) -> bool {
+        self.cores[core_id].is_at_preemption_point()
+    }
+
+    fn get_preemptable_max_and_index(&self, key: &str, node: &Node) -> Option<(i32, usize)> {
+        self.cores
+            .iter()
+            .enumerate()
+            .filter(|&(i, core)| self.is_allowed_core(i, node) && core.is_at_preemption_point())
+            .filter_map(|(i, core)| {
+                let node_data = core.processing_node.as_ref()?;
+                Some((node_data.get_value(key), i))
+            })
+            .max_by_key(|&(value, _)| value)
+    }
+
+    fn get_min_time_to_preemption_point(&self) -> Option<i32> {
+        self.cores
+            .iter()
+            .filter_map(|core| core.get_time_to_next_preemption_point())
+            .min()
+    }
+
+    fn get_executed_time(&self, core_id: usize) -> i32 {
+        self.cores[core_id].get_executed_time()
+    }
+
+    fn get_time_to_executed_time(&self, core_id: usize, executed_time: i32) -> Option<i32> {
+        self.cores[core_id].get_time_to_executed_time(executed_time)
+    }
+
+    fn update_processing_nodes(&mut self, update: impl Fn(&mut Node, i32)) {
+        for core in self.cores.iter_mut() {
+            if let Some(node) = core.processing_node.as_mut() {
+                update(node, core.remain_proc_time);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_heterogeneous_processor {
+    use super::*;
+    use crate::processor::core::WORK_SCALE;
+    use std::collections::BTreeMap;
+
+    fn create_node(execution_time: i32) -> Node {
+        Node::new(
+            0,
+            BTreeMap::from([("execution_time".to_string(), execution_time)]),
+        )
+    }
+
+    #[test]
+    fn test_get_idle_core_i_prefers_faster_core() {
+        let mut processor =
+            HeterogeneousProcessor::new_with_speeds(&[0.5, 1.0, 0.5, 1.0], Default::default());
+        assert_eq!(processor.get_idle_core_i(&create_node(4)), Some(1));
+        processor.allocate(1, &create_node(4));
+        assert_eq!(processor.get_idle_core_i(&create_node(4)), Some(3));
+        processor.allocate(3, &create_node(4));
+        assert_eq!(processor.get_idle_core_i(&create_node(4)), Some(0));
+    }
+
+    #[test]
+    fn test_process_scaled_by_speed() {
+        let mut processor =
+            HeterogeneousProcessor::new_with_speeds(&[1.0, 0.5], Default::default());
+        processor.allocate(0, &create_node(4));
+        processor.allocate(1, &create_node(4));
+        assert_eq!(processor.get_min_remain_proc_time(), Some(4));
+
+        let results = processor.process(4);
+        assert!(matches!(results[0], ProcessResult::Done(_)));
+        assert_eq!(results[1], ProcessResult::InProgress);
+        assert_eq!(processor.get_min_remain_proc_time(), Some(4));
+    }
+
+    #[test]
+    fn test_preempt_keeps_remaining_work() {
+        let mut processor =
+            HeterogeneousProcessor::new_with_speeds(&[0.5, 2.0], Default::default());
+        processor.allocate(0, &create_node(5));
+        // 10 time units on the slow core. 3 time units are 1.5 units of work, of which 1 is counted as done and 0.5 is
+        // carried.
+        processor.process(3);
+        let preempted_node = processor.preempt(0);
+        assert_eq!(preempted_node.get_value("execution_time"), 4);
+        assert_eq!(preempted_node.get_value("executed_time"), 1);
+        assert_eq!(preempted_node.get_value("partial_work"), WORK_SCALE / 2);
+
+        // ceil((4 - 0.5) / 2)
+        processor.allocate(1, &preempted_node);
+        assert_eq!(processor.get_min_remain_proc_time(), Some(2));
+    }
+
+    #[test]
+    fn test_preempt_carries_partial_work() {
+        let mut processor = HeterogeneousProcessor::new_with_speeds(&[0.5], Default::default());
+        let mut node = create_node(2);
+        // Preempting after every time unit does not lose the half unit of work done in it.
+        for _ in 0..3 {
+            processor.allocate(0, &node);
+            processor.process(1);
+            node = processor.preempt(0);
+        }
+        assert_eq!(node.get_value("execution_time"), 1);
+        assert_eq!(node.get_value("partial_work"), WORK_SCALE / 2);
+        processor.allocate(0, &node);
+        assert_eq!(processor.get_min_remain_proc_time(), Some(1));
+    }
+}
@@ -1,80 +1,20 @@
-//! Homogeneous processor module. This module uses Core struct.
+//! Homogeneous processor module. All cores run at speed 1.0, so it is the heterogeneous processor with unit speeds.
 
-use super::core::{Core, ProcessResult};
-use crate::{processor::processor_interface::Processor, task::dag::Node};
+use super::heterogeneous::HeterogeneousProcessor;
 
-#[derive(Clone, Debug)]
-pub struct HomogeneousProcessor {
-    pub cores: Vec<Core>,
-}
-
-impl Processor for HomogeneousProcessor {
-    fn new(num_cores: usize) -> Self {
-        Self {
-            cores: vec![Core::default(); num_cores],
-        }
-    }
-
-    fn allocate(&mut self, core_id: usize, node: &Node) {
-        self.cores[core_id].allocate(node)
-    }
-
-    fn process(&mut self) -> Vec<ProcessResult> {
-        #[cfg(debug_assertions)]
-        {
-            for (i, core) in self.cores.iter().enumerate() {
-                if !core.is_idle {
-                    println!("[Core {}]: Processing Node: {:?}", i, core.processing_node);
-                }
-            }
-        }
-
-        self.cores.iter_mut().map(|core| core.process()).collect()
-    }
-
-    fn get_num_cores(&self) -> usize {
-        self.cores.len()
-    }
-
-    fn get_num_idle_cores(&self) -> usize {
-        self.cores.iter().filter(|core| core.is_idle).count()
-    }
-
-    fn get_idle_core_i(&self) -> Option<usize> {
-        for (index, core) in self.cores.iter().enumerate() {
-            if core.is_idle {
-                return Some(index);
-            }
-        }
-        None
-    }
-
-    fn preempt(&mut self, core_id: usize) -> Node {
-        self.cores[core_id].preempt()
-    }
-
-    fn get_max_and_index(&self, key: &str) -> Option<(i32, usize)> {
-        self.cores
-            .iter()
-            .enumerate()
-            .filter_map(|(i, core)| {
-                let node_data = core.processing_node.as_ref()?;
-                let value = node_data.get_value(key);
-                Some((value, i))
-            })
-            .max_by_key(|&(value, _)| value)
-    }
-}
+pub type HomogeneousProcessor = HeterogeneousProcessor;
 
 #[cfg(test)]
 mod tests_homogeneous_processor {
     use super::*;
-    use std::collections::BTreeMap;
+    use crate::processor::{core::Overheads, processor_interface::Processor};
+    use crate::task::dag::Node;
+    use std::collections::{BTreeMap, BTreeSet};
 
     fn create_node(key: &str, value: Option<i32>) -> Node {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value.unwrap_or_default());
-        Node { id: 0, params }
+        Node::new(0, params)
     }
 
     #[test]
@@ -85,9 +25,87 @@ mod tests_homogeneous_processor {
         processor.allocate(0, &create_node("execution_time", Some(NODE0_ET)));
         processor.allocate(1, &create_node("execution_time", Some(NODE1_ET)));
 
+        let ready_node = create_node("execution_time", None);
         assert_eq!(
-            processor.get_max_and_index("execution_time"),
+            processor.get_max_and_index("execution_time", &ready_node),
             Some((NODE1_ET, 1))
         );
     }
+
+    #[test]
+    fn test_affinity() {
+        let mut processor = HomogeneousProcessor::new(3);
+        let mut pinned_node = create_node("execution_time", Some(10));
+        pinned_node.affinity = Some(BTreeSet::from([2]));
+        assert_eq!(processor.get_idle_core_i(&pinned_node), Some(2));
+
+        processor.allocate(1, &create_node("execution_time", Some(10)));
+        processor.allocate(2, &create_node("execution_time", Some(5)));
+        assert_eq!(processor.get_idle_core_i(&pinned_node), None);
+        assert_eq!(
+            processor.get_max_and_index("execution_time", &pinned_node),
+            Some((5, 2))
+        );
+
+        // Core ids are not limited by the width of an integer.
+        let processor = HomogeneousProcessor::new(40);
+        pinned_node.affinity = Some(BTreeSet::from([35]));
+        assert_eq!(processor.get_idle_core_i(&pinned_node), Some(35));
+    }
+
+    #[test]
+    fn test_callback_group() {
+        let mut processor = HomogeneousProcessor::new(3);
+        let mut group_node = create_node("execution_time", Some(10));
+        group_node.params.insert("callback_group".to_string(), 0);
+        processor.allocate(1, &group_node);
+        processor.allocate(2, &create_node("execution_time", Some(5)));
+
+        // Another node of the group can neither start on the idle core nor preempt a node outside the group.
+        assert_eq!(processor.get_idle_core_i(&group_node), None);
+        assert_eq!(
+            processor.get_max_and_index("execution_time", &group_node),
+            Some((10, 1))
+        );
+
+        let mut other_group_node = create_node("execution_time", Some(10));
+        other_group_node
+            .params
+            .insert("callback_group".to_string(), 1);
+        assert_eq!(processor.get_idle_core_i(&other_group_node), Some(0));
+    }
+
+    #[test]
+    fn test_get_min_remain_proc_time() {
+        let mut processor = HomogeneousProcessor::new(3);
+        assert_eq!(processor.get_min_remain_proc_time(), None);
+
+        processor.allocate(0, &create_node("execution_time", Some(10)));
+        processor.allocate(2, &create_node("execution_time", Some(4)));
+        assert_eq!(processor.get_min_remain_proc_time(), Some(4));
+
+        processor.process(3);
+        assert_eq!(processor.get_min_remain_proc_time(), Some(1));
+    }
+
+    #[test]
+    fn test_migration_overhead() {
+        let mut processor = HomogeneousProcessor::new_with_overheads(
+            2,
+            Overheads {
+                context_switch: 1,
+                migration: 2,
+                cache_related_preemption_delay: 0,
+            },
+        );
+        processor.allocate(0, &create_node("execution_time", Some(4)));
+        assert_eq!(processor.get_min_remain_proc_time(), Some(5));
+        processor.process(3);
+
+        let preempted_node = processor.preempt(0);
+        processor.allocate(1, &preempted_node);
+        assert_eq!(processor.get_min_remain_proc_time(), Some(5));
+        processor.process(5);
+        assert_eq!(processor.get_total_overhead_times(), vec![1, 3]);
+    }
 }
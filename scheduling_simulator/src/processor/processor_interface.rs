@@ -5,10 +5,55 @@ use super::core::ProcessResult;
 pub trait Processor {
     fn new(num_cores: usize) -> Self;
     fn allocate(&mut self, core_id: usize, node_data: &Node);
-    fn process(&mut self) -> Vec<ProcessResult>;
+    /// Delay the processing node of the core, e.g. while it spins on a resource.
+    fn charge_overhead(&mut self, core_id: usize, overhead: i32);
+    fn process(&mut self, time: i32) -> Vec<ProcessResult>;
     fn get_num_cores(&self) -> usize;
-    fn get_idle_core_i(&self) -> Option<usize>;
+    fn get_processing_node(&self, core_id: usize) -> Option<&Node>;
+    fn get_idle_core_i(&self, node: &Node) -> Option<usize>;
     fn get_num_idle_cores(&self) -> usize;
     fn preempt(&mut self, core_id: usize) -> Node;
-    fn get_max_and_index(&self, key: &str) -> Option<(i32, usize)>;
+    fn get_max_and_index(&self, key: &str, node: &Node) -> Option<(i32, usize)>;
+    /// Includes the overhead that the busy cores still have to consume.
+    fn get_min_remain_proc_time(&self) -> Option<i32>;
+    fn get_total_overhead_times(&self) -> Vec<i32>;
+    /// Work done by each core per unit of time. Nodes execute in `execution_time` at speed 1.0.
+    fn get_core_speeds(&self) -> Vec<f64>;
+    fn is_at_preemption_point(&self, core_id: usize) -> bool;
+    /// Same as `get_max_and_index`, but only among the cores whose nodes are at a preemption point.
+    fn get_preemptable_max_and_index(&self, key: &str, node: &Node) -> Option<(i32, usize)>;
+    fn get_min_time_to_preemption_point(&self) -> Option<i32>;
+    /// Work the processing node of the core has executed so far. 0 if the core is idle.
+    fn get_executed_time(&self, core_id: usize) -> i32;
+    /// Time until the executed time of the processing node reaches `executed_time`. None if the core is idle.
+    fn get_time_to_executed_time(&self, core_id: usize, executed_time: i32) -> Option<i32>;
+    /// Apply `update` to each processing node along with its remaining processing time on its core, i.e., its remaining
+    /// work scaled by the speed of the core, excluding overhead.
+    fn update_processing_nodes(&mut self, update: impl Fn(&mut Node, i32));
+
+    /// A node with an `affinity` may only run on the cores in it, and a node with a `partitioned_core` only on that core.
+    /// Nodes with the same `callback_group` never run in parallel, like the callbacks of a mutually exclusive callback
+    /// group in ROS 2. While a node of the group is running, the others may only run on its core by preempting it.
+    fn is_allowed_core(&self, core_id: usize, node: &Node) -> bool {
+        if let Some(affinity) = &node.affinity {
+            if !affinity.contains(&core_id) {
+                return false;
+            }
+        }
+        if let Some(&partitioned_core) = node.params.get("partitioned_core") {
+            if partitioned_core as usize != core_id {
+                return false;
+            }
+        }
+
+        match node.params.get("callback_group") {
+            Some(callback_group) => (0..self.get_num_cores())
+                .filter(|&other_core_id| other_core_id != core_id)
+                .filter_map(|other_core_id| self.get_processing_node(other_core_id))
+                .all(|running_node| {
+                    running_node.params.get("callback_group") != Some(callback_group)
+                }),
+            None => true,
+        }
+    }
 }
@@ -1,3 +1,8 @@
 pub mod dag_set_scheduler;
+pub mod edzl_scheduler;
+pub mod federated_scheduler;
 pub mod fixed_priority_scheduler;
+pub mod llf_scheduler;
+pub mod partitioning;
 pub mod proposed_edf_scheduler;
+pub mod ros2_executor_scheduler;
@@ -1,14 +1,234 @@
 use crate::{
     log::DAGSetSchedulerLog,
     processor::{core::ProcessResult, processor_interface::Processor},
-    task::dag::{Node, DAG},
+    task::{
+        arrival::{get_job_arrival, JobArrival},
+        conditional::choose_branch,
+        dag::{Node, DAG},
+    },
 };
-use petgraph::graph::Graph;
-use std::collections::VecDeque;
+use clap::ValueEnum;
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// When a node is waiting for a core held by a lower-priority node that is not at a preemption point.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum PreemptionMode {
+    /// Wait until the lowest-priority running node reaches a preemption point.
+    Lazy,
+    /// Preempt the first lower-priority running node that reaches a preemption point.
+    Eager,
+}
 
 pub enum PreemptiveType {
     NonPreemptive,
-    Preemptive { key: String },
+    Preemptive {
+        key: String,
+    },
+    /// Running nodes can only be preempted at their preemption points. See `Core::is_at_preemption_point`.
+    LimitedPreemptive {
+        key: String,
+        mode: PreemptionMode,
+    },
+}
+
+impl PreemptiveType {
+    pub fn get_key(&self) -> Option<&str> {
+        match self {
+            PreemptiveType::NonPreemptive => None,
+            PreemptiveType::Preemptive { key } | PreemptiveType::LimitedPreemptive { key, .. } => {
+                Some(key)
+            }
+        }
+    }
+}
+
+/// Precedence between consecutive jobs of the same DAG, which may overlap when `relative_deadline > period`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum IntraTaskPrecedence {
+    /// Jobs are independent of each other.
+    #[default]
+    Independent,
+    /// The source of job k + 1 cannot start before job k completes.
+    Job,
+    /// Each node of job k + 1 cannot start before the same node of job k completes.
+    Node,
+}
+
+/// When the `delay` of an edge, given as its weight, is applied between the completion of the predecessor and the
+/// start of the successor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum CommunicationDelayMode {
+    /// Always. The successor becomes ready once the data of all its predecessors has arrived.
+    #[default]
+    Always,
+    /// Only when the two nodes run on different cores. The successor becomes ready as soon as the data is available
+    /// on some core, and may only start on the cores where the data of all its predecessors has arrived.
+    CrossCore,
+}
+
+/// How nodes access the resources of their critical sections. See `Node::get_critical_sections`.
+/// A node acquires the resource of each critical section when it reaches the section and releases it when the section
+/// ends. A node that cannot acquire a resource waits in the ready queue without occupying a core, except under MrsP.
+/// Priorities are raised by lowering the value of the preemption key.
+/// The ceiling of a resource is the highest preemption level among the nodes that use it. Preemption levels are static:
+/// the preemption key when every node of the DAGSet sets it, as with fixed priorities, and otherwise the relative
+/// deadline, as in the stack resource policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ResourceProtocol {
+    /// Mutual exclusion only. A node waits until the resource is released.
+    #[default]
+    None,
+    /// Priority inheritance. A holder inherits the highest priority among the nodes waiting for its resources.
+    Pip,
+    /// Global priority ceiling. A node only acquires a resource when its preemption level is above the ceilings of all
+    /// resources held by other nodes, and a holder inherits the highest priority among the nodes it blocks.
+    Pcp,
+    /// Multiprocessor priority ceiling. A holder runs its critical section at the ceiling of the resource raised above
+    /// the priorities of all nodes.
+    Mpcp,
+    /// Multiprocessor resource sharing protocol. A node spins on its core until the resource is released, in FIFO
+    /// order. Critical sections run non-preemptively, which stands in for the helping mechanism. The spin time is
+    /// charged as overhead.
+    Mrsp,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SchedulerConfig {
+    /// Keep simulating until the end of the duration instead of stopping at the first deadline miss.
+    pub continue_after_deadline_miss: bool,
+    /// Seed used to generate the DAGSet. It is written to the log so that the run can be regenerated.
+    pub seed: u64,
+    /// Record the execution intervals of each core and dump them as a Gantt chart and a Chrome trace with the log.
+    pub record_trace: bool,
+    pub intra_task_precedence: IntraTaskPrecedence,
+    pub communication_delay: CommunicationDelayMode,
+    pub resource_protocol: ResourceProtocol,
+    /// Normalized utilization the periods were scaled to, if any. It is written to the log with the seed.
+    pub normalized_utilization: Option<f64>,
+}
+
+fn is_same_node(a: &Node, b: &Node) -> bool {
+    a.id == b.id
+        && a.get_value("dag_id") == b.get_value("dag_id")
+        && a.get_value("job_id") == b.get_value("job_id")
+}
+
+/// The critical sections of the node as (resource, start, end) in executed time. See `Node::get_critical_sections`.
+fn get_critical_section_spans(node: &Node) -> Vec<(&str, i32, i32)> {
+    let mut start = 0;
+    node.get_critical_sections()
+        .into_iter()
+        .map(|(resource, end)| {
+            let span = (resource, start, end);
+            start = end;
+            span
+        })
+        .collect()
+}
+
+/// Resources the node holds at `executed_time`, with the executed time at which it releases them.
+/// `acquired_sections` counts the critical sections whose resources the node has acquired.
+fn get_held_resources(node: &Node, executed_time: i32) -> Vec<(&str, i32)> {
+    let num_acquired_sections = node.get_value_or("acquired_sections", 0) as usize;
+    get_critical_section_spans(node)
+        .into_iter()
+        .take(num_acquired_sections)
+        .filter(|&(_, _, end)| end > executed_time)
+        .map(|(resource, _, end)| (resource, end))
+        .collect()
+}
+
+/// The resource the node has to acquire before it executes beyond `executed_time`, if it has reached the start of its
+/// next critical section.
+fn get_requested_resource(node: &Node, executed_time: i32) -> Option<&str> {
+    let num_acquired_sections = node.get_value_or("acquired_sections", 0) as usize;
+    let (resource, start, _) = get_critical_section_spans(node)
+        .into_iter()
+        .nth(num_acquired_sections)?;
+    (start <= executed_time).then_some(resource)
+}
+
+/// Whether `holder` keeps `node` from acquiring `requested_resource`: it holds the resource, or, under PCP, it holds a
+/// resource whose ceiling is not below the preemption level of the node.
+fn is_blocked_by(
+    node: &Node,
+    requested_resource: &str,
+    holder: &Node,
+    holder_executed_time: i32,
+    is_pcp: bool,
+) -> bool {
+    !is_same_node(node, holder)
+        && get_held_resources(holder, holder_executed_time)
+            .into_iter()
+            .any(|(held_resource, _)| {
+                held_resource == requested_resource
+                    || (is_pcp
+                        && node.get_value_or("preemption_level", i32::MAX)
+                            >= holder.get_value_or(&get_ceiling_key(held_resource), i32::MAX))
+            })
+}
+
+fn get_ceiling_key(resource: &str) -> String {
+    format!("ceiling.{}", resource)
+}
+
+/// Set the `preemption_level` of each node and the ceiling of each resource to the nodes that use it as
+/// `ceiling.<resource>`. Under MPCP, the ceiling is the priority of the critical sections of the resource instead,
+/// which is above the priorities of all nodes.
+fn set_resource_ceilings(
+    dag_set: &mut [Graph<Node, i32>],
+    key: Option<&str>,
+    resource_protocol: ResourceProtocol,
+) {
+    let level_key = key
+        .filter(|key| {
+            dag_set
+                .iter()
+                .flat_map(|dag| dag.node_weights())
+                .all(|node| node.params.contains_key(*key))
+        })
+        .unwrap_or("relative_deadline");
+    let mut ceilings = BTreeMap::<String, i32>::new();
+    let mut min_level = i32::MAX;
+    for dag in dag_set.iter_mut() {
+        for node in dag.node_weights_mut() {
+            let level = node.get_value_or(level_key, i32::MAX);
+            min_level = min_level.min(level);
+            for (resource, _) in node.get_critical_sections() {
+                let ceiling = ceilings.entry(resource.to_string()).or_insert(i32::MAX);
+                *ceiling = (*ceiling).min(level);
+            }
+            node.params.insert("preemption_level".to_string(), level);
+        }
+    }
+    if resource_protocol == ResourceProtocol::Mpcp {
+        // Keep the order of the ceilings, but move them below any value of the preemption key.
+        for ceiling in ceilings.values_mut() {
+            *ceiling =
+                (i32::MIN as i64 + *ceiling as i64 - min_level as i64).min(i32::MAX as i64) as i32;
+        }
+    }
+
+    for dag in dag_set.iter_mut() {
+        for node in dag.node_weights_mut() {
+            let node_ceilings = node
+                .get_critical_sections()
+                .into_iter()
+                .map(|(resource, _)| (get_ceiling_key(resource), ceilings[resource]))
+                .collect::<Vec<_>>();
+            node.params.extend(node_ceilings);
+        }
+    }
+}
+
+fn get_data_ready_time_key(core_i: usize) -> String {
+    format!("data_ready_time_on_core_{}", core_i)
+}
+
+fn set_job_arrival(dag: &mut Graph<Node, i32>, arrival: &JobArrival) {
+    dag.set_param_to_all_nodes("arrival_time", arrival.arrival_time);
+    dag.set_param_to_all_nodes("release_time", arrival.release_time);
 }
 
 pub trait DAGSetSchedulerBase<T: Processor + Clone> {
@@ -21,6 +241,8 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
     fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog;
     fn get_current_time(&self) -> i32;
     fn get_current_time_mut(&mut self) -> &mut i32;
+    fn get_config(&self) -> &SchedulerConfig;
+    fn get_config_mut(&mut self) -> &mut SchedulerConfig;
 
     // method definition
     fn new(dag_set: &[Graph<Node, i32>], processor: &T) -> Self;
@@ -34,28 +256,195 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
         uncompleted_dag_jobs: &mut Vec<Graph<Node, i32>>,
     ) {
         let current_time = self.get_current_time();
+        let seed = self.get_config().seed;
         let mut dag_set = self.get_dag_set();
 
         for dag in dag_set.iter_mut() {
             let job_i = dag.get_dag_param("job_id");
-            if current_time == dag.get_dag_param("period") * job_i {
+            if current_time == dag.get_dag_param("release_time") {
                 Self::update_params_when_release(dag, job_i);
                 ready_queue.push_back(dag[dag.get_source()].clone());
                 uncompleted_dag_jobs.push(dag.clone());
-                self.get_log_mut()
-                    .write_dag_release_time(dag.get_dag_param("dag_id") as usize, current_time);
+                // Response times are measured from the arrival time.
+                let arrival = JobArrival {
+                    arrival_time: dag.get_dag_param("arrival_time"),
+                    release_time: current_time,
+                };
+                self.get_log_mut().write_dag_release_time(
+                    dag.get_dag_param("dag_id") as usize,
+                    arrival.arrival_time,
+                );
                 dag.set_param_to_all_nodes("job_id", job_i + 1);
+                set_job_arrival(dag, &get_job_arrival(dag, seed, job_i + 1, Some(&arrival)));
             }
         }
 
         self.set_dag_set(dag_set);
     }
 
-    fn process_unit_time(&mut self) -> Vec<ProcessResult> {
-        let current_time = self.get_current_time_mut();
-        *current_time += 1;
-        let process_result = self.get_processor_mut().process();
-        self.get_log_mut().write_processing_time(&process_result);
+    fn get_next_release_time(&self) -> i32 {
+        self.get_dag_set()
+            .iter()
+            .map(|dag| dag.get_dag_param("release_time"))
+            .min()
+            .unwrap_or(i32::MAX)
+    }
+
+    fn satisfies_intra_task_precedence(
+        &self,
+        node: &Node,
+        uncompleted_dag_jobs: &[Graph<Node, i32>],
+    ) -> bool {
+        let dag_id = node.get_value("dag_id");
+        let prev_job_id = node.get_value("job_id") - 1;
+        // The previous job has completed if it is no longer in uncompleted_dag_jobs.
+        let Some(prev_job) = uncompleted_dag_jobs.iter().find(|dag| {
+            dag.get_dag_param("dag_id") == dag_id && dag.get_dag_param("job_id") == prev_job_id
+        }) else {
+            return true;
+        };
+
+        match self.get_config().intra_task_precedence {
+            IntraTaskPrecedence::Independent => true,
+            IntraTaskPrecedence::Job => false,
+            IntraTaskPrecedence::Node => {
+                prev_job[node.get_id()].params.get("completed") == Some(&1)
+            }
+        }
+    }
+
+    /// Hold back the ready nodes whose input data has not arrived yet or whose previous job has not progressed far
+    /// enough, and return the held nodes that have become ready to the ready queue.
+    fn hold_unready_nodes(
+        &self,
+        ready_queue: &mut VecDeque<Node>,
+        held_nodes: &mut VecDeque<Node>,
+        uncompleted_dag_jobs: &[Graph<Node, i32>],
+    ) {
+        let current_time = self.get_current_time();
+        ready_queue.extend(held_nodes.drain(..));
+        let (ready_nodes, new_held_nodes): (VecDeque<_>, VecDeque<_>) =
+            ready_queue.drain(..).partition(|node| {
+                node.get_value_or("data_ready_time", 0) <= current_time
+                    && self.satisfies_intra_task_precedence(node, uncompleted_dag_jobs)
+            });
+        *ready_queue = ready_nodes;
+        *held_nodes = new_held_nodes;
+    }
+
+    /// Update priority parameters that change over time. Called at every event before allocation.
+    fn update_params_before_allocation(&mut self, _ready_queue: &mut VecDeque<Node>) {}
+
+    /// Whether the scheduler may allocate the ready node at all at this event, regardless of the cores.
+    fn is_allocatable(&self, _node: &Node) -> bool {
+        true
+    }
+
+    /// The earliest time, other than releases and node completions, at which the scheduler may change its decision.
+    fn get_next_decision_time(&self, _ready_queue: &VecDeque<Node>) -> Option<i32> {
+        None
+    }
+
+    fn get_next_event_time(
+        &self,
+        preemptive_type: &PreemptiveType,
+        duration: i32,
+        ready_queue: &VecDeque<Node>,
+        held_nodes: &VecDeque<Node>,
+    ) -> i32 {
+        // Nothing changes between releases, node completions and data arrivals, so jump straight to the earliest one.
+        let mut next_event_time = duration.min(self.get_next_release_time());
+        let current_time = self.get_current_time();
+        if let Some(data_ready_time) = held_nodes
+            .iter()
+            .map(|node| node.get_value_or("data_ready_time", 0))
+            .filter(|&data_ready_time| data_ready_time > current_time)
+            .min()
+        {
+            next_event_time = next_event_time.min(data_ready_time);
+        }
+        // In the `CrossCore` mode, a ready node may start on more cores as its data arrives on them.
+        if let Some(data_ready_time) = ready_queue
+            .iter()
+            .flat_map(|node| {
+                (0..self.get_processor().get_num_cores())
+                    .filter_map(|core_i| node.params.get(&get_data_ready_time_key(core_i)))
+            })
+            .copied()
+            .filter(|&data_ready_time| data_ready_time > current_time)
+            .min()
+        {
+            next_event_time = next_event_time.min(data_ready_time);
+        }
+        if let Some(min_remain_proc_time) = self.get_processor().get_min_remain_proc_time() {
+            next_event_time = next_event_time.min(self.get_current_time() + min_remain_proc_time);
+        }
+        if let Some(next_decision_time) = self.get_next_decision_time(ready_queue) {
+            next_event_time = next_event_time.min(next_decision_time);
+        }
+        if let Some(time) = self.get_min_time_to_resource_release() {
+            next_event_time = next_event_time.min(self.get_current_time() + time);
+        }
+        if let PreemptiveType::LimitedPreemptive { .. } = preemptive_type {
+            // A waiting node may preempt when a running node reaches its next preemption point.
+            if !ready_queue.is_empty() {
+                if let Some(time) = self.get_processor().get_min_time_to_preemption_point() {
+                    next_event_time = next_event_time.min(self.get_current_time() + time);
+                }
+            }
+        }
+
+        next_event_time
+    }
+
+    /// A waiting node is blocked while a lower-priority node runs on a core it is allowed to use, or while another node
+    /// holds one of its resources. The blocking time is accumulated in the `blocking_time` parameter of the node.
+    fn accumulate_blocking_times(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+        time: i32,
+    ) {
+        let is_blocked = ready_queue
+            .iter()
+            .map(|node| {
+                self.is_blocked_on_resource(
+                    node,
+                    node.get_value_or("executed_time", 0),
+                    ready_queue,
+                ) || preemptive_type.get_key().is_some_and(|key| {
+                    self.get_processor()
+                        .get_max_and_index(key, node)
+                        .is_some_and(|(max_value, _)| max_value > node.get_value(key))
+                })
+            })
+            .collect::<Vec<_>>();
+        for (node, is_blocked) in ready_queue.iter_mut().zip(is_blocked) {
+            if is_blocked {
+                *node.params.entry("blocking_time".to_string()).or_insert(0) += time;
+            }
+        }
+    }
+
+    fn process_until_next_event(
+        &mut self,
+        preemptive_type: &PreemptiveType,
+        duration: i32,
+        ready_queue: &mut VecDeque<Node>,
+        held_nodes: &VecDeque<Node>,
+    ) -> Vec<ProcessResult> {
+        let next_event_time =
+            self.get_next_event_time(preemptive_type, duration, ready_queue, held_nodes);
+        let elapsed_time = next_event_time - self.get_current_time();
+        self.accumulate_blocking_times(preemptive_type, ready_queue, elapsed_time);
+        *self.get_current_time_mut() = next_event_time;
+        let process_result = self.get_processor_mut().process(elapsed_time);
+        let record_trace = self.get_config().record_trace;
+        let log = self.get_log_mut();
+        log.write_processing_time(&process_result, elapsed_time);
+        if record_trace {
+            log.write_finished_nodes(&process_result, next_event_time);
+        }
 
         process_result
     }
@@ -77,42 +466,477 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
             .unwrap();
         owner_dag.set_param(node.get_id(), "completed", 1);
         let current_time = self.get_current_time();
+        owner_dag.set_param(node.get_id(), "finish_time", current_time);
+        if let Some(&last_core_id) = node.params.get("last_core_id") {
+            owner_dag.set_param(node.get_id(), "last_core_id", last_core_id);
+        }
+        if let Some(&blocking_time) = node.params.get("blocking_time") {
+            self.get_log_mut().write_blocking_time(
+                node_dag_id as usize,
+                node.id as usize,
+                blocking_time,
+            );
+        }
 
+        let mut result = Ok(());
         let suc_nodes = owner_dag.get_suc(node.get_id());
         if suc_nodes.is_empty() {
             let response_time = self.get_log_mut().write_dag_finish_time(
                 node_dag_id as usize,
                 node.id as usize,
+                node_job_id,
                 current_time,
             );
-            if response_time > node.get_value("relative_deadline") {
-                println!(
-                    "Deadline missed. dag_id: {}, job_id: {}",
-                    node_dag_id, node_job_id
+            let relative_deadline = node.get_value("relative_deadline");
+            if response_time > relative_deadline {
+                if !self.get_config().continue_after_deadline_miss {
+                    println!(
+                        "Deadline missed. dag_id: {}, job_id: {}",
+                        node_dag_id, node_job_id
+                    );
+                }
+                self.get_log_mut().write_deadline_miss(
+                    node_dag_id as usize,
+                    node.id as usize,
+                    node_job_id,
+                    response_time - relative_deadline,
+                    true,
                 );
-                return Err(node_job_id);
-            }
-            if owner_dag.is_completed() {
-                uncompleted_dags.retain(|dag| {
-                    !(dag.get_dag_param("dag_id") == node_dag_id
-                        && dag.get_dag_param("job_id") == node_job_id)
-                });
+                result = Err(node_job_id);
             }
         } else {
-            for suc in suc_nodes {
-                owner_dag.set_param(
-                    suc,
-                    "pre_done_count",
-                    owner_dag[suc].get_value("pre_done_count") + 1,
-                );
-                if owner_dag.is_node_ready(suc) {
-                    ready_queue.push_back(owner_dag[suc].clone());
+            if owner_dag.is_conditional(node.get_id()) {
+                let branch_i = choose_branch(owner_dag, node.get_id(), self.get_config().seed);
+                let branch_id = owner_dag[branch_i].id;
+                owner_dag.set_param(node.get_id(), "chosen_branch", branch_id);
+            }
+            // Skipping a node resolves its successors in turn.
+            let mut resolved_nodes = vec![node.get_id()];
+            while let Some(pre_i) = resolved_nodes.pop() {
+                for suc in owner_dag.get_suc(pre_i) {
+                    owner_dag.set_param(
+                        suc,
+                        "pre_done_count",
+                        owner_dag[suc].get_value("pre_done_count") + 1,
+                    );
+                    if !owner_dag.is_node_ready(suc) {
+                        continue;
+                    }
+                    if owner_dag
+                        .get_pre(suc)
+                        .into_iter()
+                        .any(|pre_i| owner_dag.is_edge_taken(pre_i, suc))
+                    {
+                        self.set_data_ready_times(owner_dag, suc);
+                        ready_queue.push_back(owner_dag[suc].clone());
+                    } else {
+                        owner_dag.set_param(suc, "skipped", 1);
+                        owner_dag.set_param(suc, "completed", 1);
+                        resolved_nodes.push(suc);
+                    }
                 }
             }
             self.sort_ready_queue(ready_queue);
         }
+        if owner_dag.is_completed() {
+            uncompleted_dags.retain(|dag| {
+                !(dag.get_dag_param("dag_id") == node_dag_id
+                    && dag.get_dag_param("job_id") == node_job_id)
+            });
+        }
 
-        Ok(())
+        result
+    }
+
+    /// Set `data_ready_time`, the earliest time at which the data of all predecessors of the node is available on
+    /// some core. In the `CrossCore` mode, the time for each core is also set to restrict the cores the node starts on.
+    /// Only the taken edges carry data.
+    fn set_data_ready_times(&self, dag: &mut Graph<Node, i32>, node_i: NodeIndex) {
+        let arrivals = dag
+            .get_pre(node_i)
+            .into_iter()
+            .filter(|&pre_i| dag.is_edge_taken(pre_i, node_i))
+            .map(|pre_i| {
+                let edge_i = dag.find_edge(pre_i, node_i).unwrap();
+                (
+                    dag[pre_i].get_value("finish_time"),
+                    dag[edge_i],
+                    dag[pre_i].get_value("last_core_id") as usize,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let data_ready_time = match self.get_config().communication_delay {
+            CommunicationDelayMode::Always => arrivals
+                .iter()
+                .map(|&(finish_time, delay, _)| finish_time + delay)
+                .max()
+                .unwrap_or(0),
+            CommunicationDelayMode::CrossCore => {
+                let data_ready_times = (0..self.get_processor().get_num_cores())
+                    .map(|core_i| {
+                        arrivals
+                            .iter()
+                            .map(|&(finish_time, delay, pre_core_i)| {
+                                if pre_core_i == core_i {
+                                    finish_time
+                                } else {
+                                    finish_time + delay
+                                }
+                            })
+                            .max()
+                            .unwrap_or(0)
+                    })
+                    .collect::<Vec<_>>();
+                for (core_i, &time) in data_ready_times.iter().enumerate() {
+                    dag.set_param(node_i, &get_data_ready_time_key(core_i), time);
+                }
+                *data_ready_times.iter().min().unwrap()
+            }
+        };
+        dag.set_param(node_i, "data_ready_time", data_ready_time);
+    }
+
+    /// In the `CrossCore` mode, a node that has not started yet may only start on the cores where the data of all its
+    /// predecessors has arrived. Returns the node with its affinity restricted to these cores.
+    fn restrict_to_data_ready_cores(&self, node: &Node) -> Node {
+        let mut node = node.clone();
+        if self.get_config().communication_delay != CommunicationDelayMode::CrossCore
+            || node.params.contains_key("is_preempted")
+        {
+            return node;
+        }
+        let current_time = self.get_current_time();
+        let data_ready_cores = (0..self.get_processor().get_num_cores())
+            .filter(|&core_i| {
+                node.params
+                    .get(&get_data_ready_time_key(core_i))
+                    .is_none_or(|&data_ready_time| data_ready_time <= current_time)
+            })
+            .collect::<BTreeSet<_>>();
+        node.affinity = Some(match &node.affinity {
+            Some(affinity) => affinity & &data_ready_cores,
+            None => data_ready_cores,
+        });
+        node
+    }
+
+    /// A node that has reached a critical section acquires its resource. Under MrsP, it first spins on the core until
+    /// the resource is released.
+    fn allocate_node(&mut self, core_i: usize, node: &Node) {
+        let mut node = node.clone();
+        let mut spin_time = 0;
+        if let Some(resource) = get_requested_resource(&node, node.get_value_or("executed_time", 0))
+        {
+            if self.get_config().resource_protocol == ResourceProtocol::Mrsp {
+                spin_time = self.get_spin_time(resource);
+            }
+            *node
+                .params
+                .entry("acquired_sections".to_string())
+                .or_insert(0) += 1;
+        }
+        if spin_time > 0 {
+            *node.params.entry("blocking_time".to_string()).or_insert(0) += spin_time;
+        }
+        let processor = self.get_processor_mut();
+        processor.allocate(core_i, &node);
+        processor.charge_overhead(core_i, spin_time);
+    }
+
+    /// Running nodes that have reached a critical section acquire its resource, in the order of their priorities.
+    /// Under MrsP, a node spins on its core until the resource is released, and otherwise a blocked node is suspended
+    /// to the ready queue.
+    fn acquire_resources(
+        &mut self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+    ) {
+        let processor = self.get_processor();
+        let mut requesting_cores = (0..processor.get_num_cores())
+            .filter(|&core_i| {
+                processor.get_processing_node(core_i).is_some_and(|node| {
+                    get_requested_resource(node, processor.get_executed_time(core_i)).is_some()
+                })
+            })
+            .collect::<Vec<_>>();
+        if let Some(key) = preemptive_type.get_key() {
+            requesting_cores.sort_by_key(|&core_i| {
+                processor
+                    .get_processing_node(core_i)
+                    .unwrap()
+                    .get_value_or(key, i32::MAX)
+            });
+        }
+
+        let current_time = self.get_current_time();
+        let is_mrsp = self.get_config().resource_protocol == ResourceProtocol::Mrsp;
+        for core_i in requesting_cores {
+            loop {
+                let processor = self.get_processor();
+                let node = processor.get_processing_node(core_i).unwrap();
+                let executed_time = processor.get_executed_time(core_i);
+                let Some(resource) = get_requested_resource(node, executed_time) else {
+                    break;
+                };
+                let spin_time = if is_mrsp {
+                    self.get_spin_time(resource)
+                } else if self.is_blocked_on_resource(node, executed_time, ready_queue) {
+                    ready_queue.push_back(self.get_processor_mut().preempt(core_i));
+                    if self.get_config().record_trace {
+                        self.get_log_mut()
+                            .write_preempted_node(core_i, current_time);
+                    }
+                    break;
+                } else {
+                    0
+                };
+
+                let node = node.clone();
+                let processor = self.get_processor_mut();
+                processor.update_processing_nodes(|processing_node, _| {
+                    if is_same_node(processing_node, &node) {
+                        *processing_node
+                            .params
+                            .entry("acquired_sections".to_string())
+                            .or_insert(0) += 1;
+                        if spin_time > 0 {
+                            *processing_node
+                                .params
+                                .entry("blocking_time".to_string())
+                                .or_insert(0) += spin_time;
+                        }
+                    }
+                });
+                processor.charge_overhead(core_i, spin_time);
+            }
+        }
+    }
+
+    /// Nodes that may hold resources, i.e., the running nodes and the preempted nodes in the ready queue, with their
+    /// executed times.
+    fn get_started_nodes<'a>(&'a self, ready_queue: &'a VecDeque<Node>) -> Vec<(&'a Node, i32)>
+    where
+        T: 'a,
+    {
+        let processor = self.get_processor();
+        (0..processor.get_num_cores())
+            .filter_map(|core_i| {
+                let node = processor.get_processing_node(core_i)?;
+                Some((node, processor.get_executed_time(core_i)))
+            })
+            .chain(
+                ready_queue
+                    .iter()
+                    .filter(|node| node.params.contains_key("is_preempted"))
+                    .map(|node| (node, node.get_value("executed_time"))),
+            )
+            .collect()
+    }
+
+    /// A node that has reached a critical section is blocked while another node holds its resource. Under PCP, it is
+    /// also blocked while its preemption level is not above the ceiling of a resource held by another node.
+    fn is_blocked_on_resource(
+        &self,
+        node: &Node,
+        executed_time: i32,
+        ready_queue: &VecDeque<Node>,
+    ) -> bool {
+        let Some(requested_resource) = get_requested_resource(node, executed_time) else {
+            return false;
+        };
+        let is_pcp = self.get_config().resource_protocol == ResourceProtocol::Pcp;
+        self.get_started_nodes(ready_queue)
+            .into_iter()
+            .any(|(holder, holder_executed_time)| {
+                is_blocked_by(
+                    node,
+                    requested_resource,
+                    holder,
+                    holder_executed_time,
+                    is_pcp,
+                )
+            })
+    }
+
+    /// Time until a running node releases a resource or reaches its next critical section.
+    fn get_min_time_to_resource_release(&self) -> Option<i32> {
+        let processor = self.get_processor();
+        (0..processor.get_num_cores())
+            .filter_map(|core_i| {
+                let node = processor.get_processing_node(core_i)?;
+                let executed_time = processor.get_executed_time(core_i);
+                let num_acquired_sections = node.get_value_or("acquired_sections", 0) as usize;
+                let boundary = get_critical_section_spans(node)
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(section_i, (_, start, end))| {
+                        if section_i < num_acquired_sections {
+                            Some(end)
+                        } else if section_i == num_acquired_sections {
+                            Some(start)
+                        } else {
+                            None
+                        }
+                    })
+                    .filter(|&boundary| boundary > executed_time)
+                    .min()?;
+                processor.get_time_to_executed_time(core_i, boundary)
+            })
+            .min()
+    }
+
+    /// Under MrsP, the time a node spins until `resource` is released by the running nodes, which run their critical
+    /// sections non-preemptively.
+    fn get_spin_time(&self, resource: &str) -> i32 {
+        let processor = self.get_processor();
+        (0..processor.get_num_cores())
+            .filter_map(|core_i| {
+                let holder = processor.get_processing_node(core_i)?;
+                get_held_resources(holder, processor.get_executed_time(core_i))
+                    .into_iter()
+                    .filter(|&(held_resource, _)| held_resource == resource)
+                    .filter_map(|(_, end)| processor.get_time_to_executed_time(core_i, end))
+                    .max()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Restore the priorities raised by `raise_resource_holder_priorities` at the previous event.
+    fn restore_resource_holder_priorities(
+        &mut self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+    ) {
+        let Some(key) = preemptive_type.get_key() else {
+            return;
+        };
+        let restore = |node: &mut Node, _| {
+            if let Some(priority) = node.params.remove("unraised_priority") {
+                node.params.insert(key.to_string(), priority);
+            }
+        };
+        for node in ready_queue.iter_mut() {
+            restore(node, 0);
+        }
+        self.get_processor_mut().update_processing_nodes(restore);
+    }
+
+    /// Raise the priorities of the resource holders according to the resource protocol.
+    fn raise_resource_holder_priorities(
+        &mut self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+    ) {
+        let Some(key) = preemptive_type.get_key() else {
+            return;
+        };
+        let resource_protocol = self.get_config().resource_protocol;
+        if resource_protocol == ResourceProtocol::None {
+            return;
+        }
+
+        // The nodes that wait for a resource, with the resource
+        let started_nodes = self.get_started_nodes(ready_queue);
+        let waiting_nodes = ready_queue
+            .iter()
+            .filter_map(|node| {
+                let requested_resource =
+                    get_requested_resource(node, node.get_value_or("executed_time", 0))?;
+                Some((node, requested_resource))
+            })
+            .collect::<Vec<_>>();
+
+        let raised_priorities = started_nodes
+            .iter()
+            .filter_map(|&(holder, executed_time)| {
+                let held_resources = get_held_resources(holder, executed_time);
+                if held_resources.is_empty() {
+                    return None;
+                }
+                let raised_priority = match resource_protocol {
+                    ResourceProtocol::None => return None,
+                    ResourceProtocol::Pip | ResourceProtocol::Pcp => waiting_nodes
+                        .iter()
+                        .filter(|&&(node, requested_resource)| {
+                            is_blocked_by(
+                                node,
+                                requested_resource,
+                                holder,
+                                executed_time,
+                                resource_protocol == ResourceProtocol::Pcp,
+                            )
+                        })
+                        .map(|(node, _)| node.get_value_or(key, i32::MAX))
+                        .min()?,
+                    ResourceProtocol::Mpcp => held_resources
+                        .iter()
+                        .map(|(resource, _)| {
+                            holder.get_value_or(&get_ceiling_key(resource), i32::MAX)
+                        })
+                        .min()?,
+                    ResourceProtocol::Mrsp => i32::MIN,
+                };
+                Some((
+                    (
+                        holder.id,
+                        holder.get_value("dag_id"),
+                        holder.get_value("job_id"),
+                    ),
+                    raised_priority,
+                ))
+            })
+            .collect::<Vec<_>>();
+        if raised_priorities.is_empty() {
+            return;
+        }
+
+        let raise = |node: &mut Node, _| {
+            let Some(&(_, raised_priority)) =
+                raised_priorities.iter().find(|((id, dag_id, job_id), _)| {
+                    node.id == *id
+                        && node.get_value("dag_id") == *dag_id
+                        && node.get_value("job_id") == *job_id
+                })
+            else {
+                return;
+            };
+            let priority = node.get_value_or(key, i32::MAX);
+            if raised_priority < priority {
+                node.params
+                    .entry("unraised_priority".to_string())
+                    .or_insert(priority);
+                node.params.insert(key.to_string(), raised_priority);
+            }
+        };
+        for node in ready_queue.iter_mut() {
+            raise(node, 0);
+        }
+        self.get_processor_mut().update_processing_nodes(raise);
+        self.sort_by_raised_priority(preemptive_type, ready_queue);
+    }
+
+    /// Move the waiting holders with raised priorities forward, keeping the order of the scheduler otherwise.
+    fn sort_by_raised_priority(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+    ) {
+        if let (
+            Some(key),
+            ResourceProtocol::Pip
+            | ResourceProtocol::Pcp
+            | ResourceProtocol::Mpcp
+            | ResourceProtocol::Mrsp,
+        ) = (
+            preemptive_type.get_key(),
+            self.get_config().resource_protocol,
+        ) {
+            ready_queue
+                .make_contiguous()
+                .sort_by_key(|node| node.get_value_or(key, i32::MAX));
+        }
     }
 
     fn can_preempt(
@@ -120,38 +944,145 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
         preemptive_type: &PreemptiveType,
         ready_head_node: &Node,
     ) -> Option<usize> {
-        if let PreemptiveType::Preemptive {
-            key: preemptive_key,
-        } = &preemptive_type
-        {
-            let (max_value, core_i) = self
-                .get_processor()
-                .get_max_and_index(preemptive_key)
-                .unwrap();
+        // None if the node is not allowed to run on any busy core.
+        let processor = self.get_processor();
+        let (key, (max_value, core_i)) = match preemptive_type {
+            PreemptiveType::NonPreemptive => return None,
+            PreemptiveType::Preemptive { key } => {
+                (key, processor.get_max_and_index(key, ready_head_node)?)
+            }
+            PreemptiveType::LimitedPreemptive {
+                key,
+                mode: PreemptionMode::Lazy,
+            } => {
+                let (max_value, core_i) = processor.get_max_and_index(key, ready_head_node)?;
+                if !processor.is_at_preemption_point(core_i) {
+                    return None;
+                }
+                (key, (max_value, core_i))
+            }
+            PreemptiveType::LimitedPreemptive {
+                key,
+                mode: PreemptionMode::Eager,
+            } => (
+                key,
+                processor.get_preemptable_max_and_index(key, ready_head_node)?,
+            ),
+        };
+
+        if max_value > ready_head_node.get_value(key) {
+            Some(core_i)
+        } else {
+            None
+        }
+    }
 
-            if max_value > ready_head_node.get_value(preemptive_key) {
-                return Some(core_i);
+    fn allocate_ready_nodes(
+        &mut self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &mut VecDeque<Node>,
+    ) {
+        // Allocate nodes as long as there are idle cores, and attempt to preempt when all cores are busy.
+        // A node that cannot be allocated due to the scheduler, its affinity or callback group is skipped so that the
+        // following nodes still get a chance.
+        // Under MrsP, a node blocked on a resource starts and spins, and otherwise it waits.
+        let current_time = self.get_current_time();
+        let record_trace = self.get_config().record_trace;
+        let is_mrsp = self.get_config().resource_protocol == ResourceProtocol::Mrsp;
+        let mut ready_i = 0;
+        while ready_i < ready_queue.len() {
+            if !self.is_allocatable(&ready_queue[ready_i])
+                || (!is_mrsp
+                    && self.is_blocked_on_resource(
+                        &ready_queue[ready_i],
+                        ready_queue[ready_i].get_value_or("executed_time", 0),
+                        ready_queue,
+                    ))
+            {
+                ready_i += 1;
+                continue;
+            }
+            let candidate = self.restrict_to_data_ready_cores(&ready_queue[ready_i]);
+            if let Some(idle_core_i) = self.get_processor().get_idle_core_i(&candidate) {
+                let node = ready_queue.remove(ready_i).unwrap();
+                self.allocate_node(idle_core_i, &node);
+                if record_trace {
+                    self.get_log_mut()
+                        .write_allocating_node(idle_core_i, &node, current_time);
+                }
+            } else if let Some(core_i) = self.can_preempt(preemptive_type, &candidate) {
+                // Preempt the node with the lowest priority
+                let node = ready_queue.remove(ready_i).unwrap();
+                ready_queue.push_back(self.get_processor_mut().preempt(core_i));
+                self.allocate_node(core_i, &node);
+                if record_trace {
+                    let log = self.get_log_mut();
+                    log.write_preempted_node(core_i, current_time);
+                    log.write_allocating_node(core_i, &node, current_time);
+                }
+                self.sort_ready_queue(ready_queue);
+                self.sort_by_raised_priority(preemptive_type, ready_queue);
+                ready_i = 0;
+            } else {
+                ready_i += 1;
             }
         }
+    }
 
-        None
+    fn write_unfinished_deadline_misses(&mut self, uncompleted_dag_jobs: &[Graph<Node, i32>]) {
+        let current_time = self.get_current_time();
+        for dag in uncompleted_dag_jobs {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            let job_id = dag.get_dag_param("job_id");
+            let release_time = self.get_log().dag_set_log[dag_id].get_release_time(job_id);
+            for sink_i in dag.get_sink() {
+                if dag[sink_i].params.get("completed") == Some(&1) {
+                    continue;
+                }
+                let absolute_deadline = release_time + dag[sink_i].get_value("relative_deadline");
+                if current_time > absolute_deadline {
+                    self.get_log_mut().write_deadline_miss(
+                        dag_id,
+                        dag[sink_i].id as usize,
+                        job_id,
+                        current_time - absolute_deadline,
+                        false,
+                    );
+                }
+            }
+        }
     }
 
     fn calculate_log(&mut self, deadline_missed: bool, missed_job_id: Option<i32>) {
         let current_time = self.get_current_time();
+        let seed = self.get_config().seed;
+        let normalized_utilization = self.get_config().normalized_utilization;
+        let total_overhead_times = self.get_processor().get_total_overhead_times();
+        let core_speeds = self.get_processor().get_core_speeds();
         let log = self.get_log_mut();
+        log.seed = seed;
+        log.normalized_utilization = normalized_utilization;
+        log.write_overhead_times(&total_overhead_times);
+        log.write_core_speeds(&core_speeds);
         log.calculate_utilization(current_time);
         log.calc_response_times();
+        log.calc_deadline_miss_stats();
         log.deadline_missed = deadline_missed;
         log.missed_job_id = missed_job_id;
     }
 
     fn schedule(&mut self, preemptive_type: PreemptiveType, duration: i32) -> i32 {
         // Initialize job_id
+        let seed = self.get_config().seed;
         let mut dag_set = self.get_dag_set();
         for dag in dag_set.iter_mut() {
             dag.set_param_to_all_nodes("job_id", 0);
-            dag.set_param_to_all_nodes("pre_done_count", 0)
+            dag.set_param_to_all_nodes("pre_done_count", 0);
+            set_job_arrival(dag, &get_job_arrival(dag, seed, 0, None));
+        }
+        let resource_protocol = self.get_config().resource_protocol;
+        if let ResourceProtocol::Pcp | ResourceProtocol::Mpcp = resource_protocol {
+            set_resource_ceilings(&mut dag_set, preemptive_type.get_key(), resource_protocol);
         }
         self.set_dag_set(dag_set);
 
@@ -159,33 +1090,28 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
         let mut deadline_missed = false;
         let mut missed_job_id = None;
         let mut ready_queue = VecDeque::new();
+        let mut held_nodes = VecDeque::new();
         let mut uncompleted_dag_jobs = Vec::new();
 
         'outer: while self.get_current_time() < duration {
             // Release DAGs
             self.release_dags(&mut ready_queue, &mut uncompleted_dag_jobs);
+            self.hold_unready_nodes(&mut ready_queue, &mut held_nodes, &uncompleted_dag_jobs);
+            self.restore_resource_holder_priorities(&preemptive_type, &mut ready_queue);
+            self.acquire_resources(&preemptive_type, &mut ready_queue);
+            self.update_params_before_allocation(&mut ready_queue);
             self.sort_ready_queue(&mut ready_queue);
+            self.raise_resource_holder_priorities(&preemptive_type, &mut ready_queue);
 
-            // Allocate nodes as long as there are idle cores, and attempt to preempt when all cores are busy.
-            while !ready_queue.is_empty() {
-                if let Some(idle_core_i) = self.get_processor().get_idle_core_i() {
-                    self.get_processor_mut()
-                        .allocate(idle_core_i, &ready_queue.pop_front().unwrap());
-                } else if let Some(core_i) =
-                    self.can_preempt(&preemptive_type, ready_queue.front().unwrap())
-                {
-                    // Preempt the node with the lowest priority
-                    let processor = self.get_processor_mut();
-                    ready_queue.push_back(processor.preempt(core_i));
-                    processor.allocate(core_i, &ready_queue.pop_front().unwrap());
-                    self.sort_ready_queue(&mut ready_queue);
-                } else {
-                    break; // No core is idle and can not preempt. Exit the loop.
-                }
-            }
+            self.allocate_ready_nodes(&preemptive_type, &mut ready_queue);
 
-            // Process unit time
-            let process_result = self.process_unit_time();
+            // Process until the next event
+            let process_result = self.process_until_next_event(
+                &preemptive_type,
+                duration,
+                &mut ready_queue,
+                &held_nodes,
+            );
 
             // Post-process on completion of node execution
             for result in process_result.iter() {
@@ -193,27 +1119,59 @@ pub trait DAGSetSchedulerBase<T: Processor + Clone> {
                     if let Err(missed_job_i) =
                         self.node_completion(node_data, &mut ready_queue, &mut uncompleted_dag_jobs)
                     {
-                        deadline_missed = true;
-                        missed_job_id = Some(missed_job_i);
-                        break 'outer;
+                        if !deadline_missed {
+                            deadline_missed = true;
+                            missed_job_id = Some(missed_job_i);
+                        }
+                        if !self.get_config().continue_after_deadline_miss {
+                            break 'outer;
+                        }
                     }
                 }
             }
             self.sort_ready_queue(&mut ready_queue);
         }
 
+        if self.get_config().continue_after_deadline_miss {
+            // Jobs that are still running at the end of the simulation may already be late.
+            self.write_unfinished_deadline_misses(&uncompleted_dag_jobs);
+        }
+        if self.get_config().record_trace {
+            let current_time = self.get_current_time();
+            self.get_log_mut().write_unfinished_nodes(current_time);
+        }
         self.calculate_log(deadline_missed, missed_job_id);
         self.get_current_time()
     }
 
     fn dump_log(&mut self, dir_path: &str, alg_name: &str, verbose: bool) {
         self.get_log_mut().dump_to_yaml(dir_path, alg_name, verbose);
+        if self.get_config().record_trace {
+            let log = self.get_log();
+            log.dump_gantt_chart_svg(dir_path, alg_name);
+            log.dump_chrome_trace_json(dir_path, alg_name);
+        }
     }
 }
 
+/// `dag_set_scheduler_common!(T)` implements the accessors and `new`.
+/// `dag_set_scheduler_common!(T, accessors)` implements only the accessors for schedulers that need their own `new`.
 #[macro_export]
 macro_rules! dag_set_scheduler_common {
     { $t:ty } => {
+        $crate::dag_set_scheduler_common!($t, accessors);
+
+        fn new(dag_set: &[Graph<Node, i32>], processor: &$t) -> Self {
+            Self {
+                dag_set: dag_set.to_vec(),
+                processor: processor.clone(),
+                log: DAGSetSchedulerLog::new(dag_set, processor.get_num_cores()),
+                current_time: 0,
+                config: Default::default(),
+            }
+        }
+    };
+    { $t:ty, accessors } => {
         fn get_dag_set(&self) -> Vec<Graph<Node, i32>>{
             self.dag_set.clone()
         }
@@ -238,14 +1196,11 @@ macro_rules! dag_set_scheduler_common {
         fn get_current_time_mut(&mut self) -> &mut i32{
             &mut self.current_time
         }
-
-        fn new(dag_set: &[Graph<Node, i32>], processor: &$t) -> Self {
-            Self {
-                dag_set: dag_set.to_vec(),
-                processor: processor.clone(),
-                log: DAGSetSchedulerLog::new(dag_set, processor.get_num_cores()),
-                current_time: 0,
-            }
+        fn get_config(&self) -> &SchedulerConfig{
+            &self.config
         }
-    }
+        fn get_config_mut(&mut self) -> &mut SchedulerConfig{
+            &mut self.config
+        }
+    };
 }
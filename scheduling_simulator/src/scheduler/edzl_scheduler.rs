@@ -0,0 +1,89 @@
+//! Earliest Deadline until Zero Laxity (EDZL).
+//! Nodes are prioritized by `ref_absolute_deadline` as in the extended GEDF, except that a node whose laxity has
+//! reached zero gets the highest priority. The laxity is defined as in `llf_scheduler`.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use crate::dag_set_scheduler_common;
+use crate::log::DAGSetSchedulerLog;
+use crate::processor::homogeneous::HomogeneousProcessor;
+use crate::processor::processor_interface::Processor;
+use crate::task::dag::Node;
+use petgraph::graph::Graph;
+
+use super::dag_set_scheduler::{DAGSetSchedulerBase, SchedulerConfig};
+use super::llf_scheduler::{get_latest_start_time, set_latest_start_times};
+use super::proposed_edf_scheduler::set_ref_absolute_deadlines;
+
+const ZERO_LAXITY_PRIORITY: i32 = i32::MIN;
+
+fn get_edzl_priority(node: &Node, latest_start_time: i32, current_time: i32) -> i32 {
+    if latest_start_time <= current_time {
+        ZERO_LAXITY_PRIORITY
+    } else {
+        node.get_value("ref_absolute_deadline")
+    }
+}
+
+pub struct EDZLScheduler<T: Processor + Clone = HomogeneousProcessor> {
+    dag_set: Vec<Graph<Node, i32>>,
+    processor: T,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    config: SchedulerConfig,
+}
+
+impl<T: Processor + Clone> DAGSetSchedulerBase<T> for EDZLScheduler<T> {
+    dag_set_scheduler_common!(T);
+
+    fn update_params_when_release(dag: &mut Graph<Node, i32>, _job_id: i32) {
+        set_ref_absolute_deadlines(dag);
+        set_latest_start_times(dag);
+    }
+
+    fn update_params_before_allocation(&mut self, ready_queue: &mut VecDeque<Node>) {
+        let current_time = self.current_time;
+        for node in ready_queue.iter_mut() {
+            let latest_start_time = get_latest_start_time(node, &self.processor);
+            let edzl_priority = get_edzl_priority(node, latest_start_time, current_time);
+            node.params
+                .insert("edzl_priority".to_string(), edzl_priority);
+        }
+        self.processor
+            .update_processing_nodes(|node, remain_proc_time| {
+                let latest_start_time = node.get_value("latest_finish_time") - remain_proc_time;
+                let edzl_priority = get_edzl_priority(node, latest_start_time, current_time);
+                node.params
+                    .insert("edzl_priority".to_string(), edzl_priority);
+            });
+    }
+
+    fn get_next_decision_time(&self, ready_queue: &VecDeque<Node>) -> Option<i32> {
+        // The time at which the laxity of a waiting node reaches zero.
+        ready_queue
+            .iter()
+            .map(|node| get_latest_start_time(node, &self.processor))
+            .filter(|&latest_start_time| latest_start_time > self.current_time)
+            .min()
+    }
+
+    fn sort_ready_queue(&self, ready_queue: &mut VecDeque<Node>) {
+        let current_time = self.current_time;
+        ready_queue.make_contiguous().sort_by(|a, b| {
+            let a_priority =
+                get_edzl_priority(a, get_latest_start_time(a, &self.processor), current_time);
+            let b_priority =
+                get_edzl_priority(b, get_latest_start_time(b, &self.processor), current_time);
+            match a_priority.cmp(&b_priority) {
+                // If the priorities are equal, compare by ref_absolute_deadline, id and dag_id
+                Ordering::Equal => a
+                    .get_value("ref_absolute_deadline")
+                    .cmp(&b.get_value("ref_absolute_deadline"))
+                    .then(a.id.cmp(&b.id))
+                    .then(a.get_value("dag_id").cmp(&b.get_value("dag_id"))),
+                other => other,
+            }
+        });
+    }
+}
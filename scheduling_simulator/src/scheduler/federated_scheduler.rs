@@ -0,0 +1,193 @@
+//! Federated scheduling (Li et al., ECRTS 2014).
+//! Heavy DAGs get dedicated cores, and light DAGs share the remaining cores under the extended GEDF.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use crate::dag_set_scheduler_common;
+use crate::log::DAGSetSchedulerLog;
+use crate::processor::homogeneous::HomogeneousProcessor;
+use crate::processor::processor_interface::Processor;
+use crate::task::dag::{Node, DAG};
+use num_integer::div_ceil;
+use petgraph::graph::Graph;
+
+use super::dag_set_scheduler::{DAGSetSchedulerBase, SchedulerConfig};
+use super::proposed_edf_scheduler::{set_ref_absolute_deadlines, sort_by_ref_absolute_deadline};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FederatedAllocationError {
+    CriticalPathExceedsDeadline {
+        dag_id: i32,
+        critical_path_length: i32,
+        deadline: i32,
+    },
+    InsufficientCores {
+        required: usize,
+        num_cores: usize,
+    },
+    /// The `affinity` of the node does not include any of the cores allocated to its DAG.
+    AffinityConflict {
+        dag_id: i32,
+        node_id: i32,
+    },
+}
+
+impl fmt::Display for FederatedAllocationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FederatedAllocationError::CriticalPathExceedsDeadline {
+                dag_id,
+                critical_path_length,
+                deadline,
+            } => write!(
+                f,
+                "The critical path length {} of DAG {} exceeds its deadline {}.",
+                critical_path_length, dag_id, deadline
+            ),
+            FederatedAllocationError::InsufficientCores {
+                required,
+                num_cores,
+            } => write!(
+                f,
+                "Federated scheduling requires {} cores, but only {} cores are available.",
+                required, num_cores
+            ),
+            FederatedAllocationError::AffinityConflict { dag_id, node_id } => write!(
+                f,
+                "The affinity of node {} of DAG {} excludes all cores allocated to the DAG.",
+                node_id, dag_id
+            ),
+        }
+    }
+}
+
+/// The deadline used for core allocation is the earliest sink deadline.
+fn get_federated_deadline(dag: &Graph<Node, i32>) -> i32 {
+    dag.get_sink()
+        .iter()
+        .map(|&sink_i| dag[sink_i].get_value("relative_deadline"))
+        .min()
+        .unwrap()
+}
+
+/// Returns the cores each DAG may run on, in the order of `dag_set`.
+/// A heavy DAG (volume > min(deadline, period)) gets max(1, ceil((C - L) / (D - L))) dedicated cores per in-flight
+/// job, where a chain (C = L) still needs a core, and up to ceil(D / T) jobs are in flight when the deadline is longer
+/// than the period. Light DAGs share the rest.
+pub fn allocate_cores(
+    dag_set: &[Graph<Node, i32>],
+    num_cores: usize,
+) -> Result<Vec<Vec<usize>>, FederatedAllocationError> {
+    let mut num_dedicated_cores = Vec::with_capacity(dag_set.len());
+    for dag in dag_set {
+        let volume = dag.get_volume();
+        let critical_path_length = dag.get_critical_path_length();
+        let deadline = get_federated_deadline(dag);
+        let period = dag.get_dag_param("period");
+
+        if volume <= deadline.min(period) {
+            num_dedicated_cores.push(None);
+        } else if critical_path_length >= deadline {
+            return Err(FederatedAllocationError::CriticalPathExceedsDeadline {
+                dag_id: dag.get_dag_param("dag_id"),
+                critical_path_length,
+                deadline,
+            });
+        } else {
+            let num_cores_per_job = div_ceil(
+                volume - critical_path_length,
+                deadline - critical_path_length,
+            )
+            .max(1);
+            let num_in_flight_jobs = div_ceil(deadline, period);
+            num_dedicated_cores.push(Some((num_cores_per_job * num_in_flight_jobs) as usize));
+        }
+    }
+
+    let num_heavy_cores = num_dedicated_cores.iter().flatten().sum::<usize>();
+    let has_light_dag = num_dedicated_cores.iter().any(Option::is_none);
+    let required = num_heavy_cores + has_light_dag as usize;
+    if required > num_cores {
+        return Err(FederatedAllocationError::InsufficientCores {
+            required,
+            num_cores,
+        });
+    }
+
+    let shared_cores = (num_heavy_cores..num_cores).collect::<Vec<_>>();
+    let mut next_core_i = 0;
+    Ok(num_dedicated_cores
+        .into_iter()
+        .map(|num_cores_i| match num_cores_i {
+            Some(num_cores_i) => {
+                let dedicated_cores = (next_core_i..next_core_i + num_cores_i).collect();
+                next_core_i += num_cores_i;
+                dedicated_cores
+            }
+            None => shared_cores.clone(),
+        })
+        .collect())
+}
+
+pub struct FederatedScheduler {
+    dag_set: Vec<Graph<Node, i32>>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    config: SchedulerConfig,
+}
+
+impl FederatedScheduler {
+    pub fn try_new(
+        dag_set: &[Graph<Node, i32>],
+        processor: &HomogeneousProcessor,
+    ) -> Result<Self, FederatedAllocationError> {
+        let allocated_cores = allocate_cores(dag_set, processor.get_num_cores())?;
+        let mut dag_set = dag_set.to_vec();
+        for (dag, cores) in dag_set.iter_mut().zip(allocated_cores) {
+            for node in dag.node_weights_mut() {
+                // Nodes with their own affinity are restricted to the allocated cores within it.
+                let affinity = match &node.affinity {
+                    Some(affinity) => cores
+                        .iter()
+                        .filter(|core_i| affinity.contains(core_i))
+                        .copied()
+                        .collect::<BTreeSet<_>>(),
+                    None => cores.iter().copied().collect(),
+                };
+                if affinity.is_empty() {
+                    return Err(FederatedAllocationError::AffinityConflict {
+                        dag_id: node.get_value("dag_id"),
+                        node_id: node.id,
+                    });
+                }
+                node.affinity = Some(affinity);
+            }
+        }
+
+        Ok(Self {
+            log: DAGSetSchedulerLog::new(&dag_set, processor.get_num_cores()),
+            dag_set,
+            processor: processor.clone(),
+            current_time: 0,
+            config: Default::default(),
+        })
+    }
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for FederatedScheduler {
+    dag_set_scheduler_common!(HomogeneousProcessor, accessors);
+
+    fn new(dag_set: &[Graph<Node, i32>], processor: &HomogeneousProcessor) -> Self {
+        Self::try_new(dag_set, processor).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn update_params_when_release(dag: &mut Graph<Node, i32>, _job_id: i32) {
+        set_ref_absolute_deadlines(dag);
+    }
+
+    fn sort_ready_queue(&self, ready_queue: &mut VecDeque<Node>) {
+        sort_by_ref_absolute_deadline(ready_queue);
+    }
+}
@@ -7,17 +7,18 @@ use crate::processor::processor_interface::Processor;
 use crate::task::dag::Node;
 use petgraph::graph::Graph;
 
-use super::dag_set_scheduler::DAGSetSchedulerBase;
+use super::dag_set_scheduler::{DAGSetSchedulerBase, SchedulerConfig};
 
-pub struct FixedPriorityScheduler {
+pub struct FixedPriorityScheduler<T: Processor + Clone = HomogeneousProcessor> {
     dag_set: Vec<Graph<Node, i32>>,
-    processor: HomogeneousProcessor,
+    processor: T,
     log: DAGSetSchedulerLog,
     current_time: i32,
+    config: SchedulerConfig,
 }
 
-impl DAGSetSchedulerBase<HomogeneousProcessor> for FixedPriorityScheduler {
-    dag_set_scheduler_common!(HomogeneousProcessor);
+impl<T: Processor + Clone> DAGSetSchedulerBase<T> for FixedPriorityScheduler<T> {
+    dag_set_scheduler_common!(T);
 
     fn update_params_when_release(_dag: &mut Graph<Node, i32>, _job_id: i32) {
         // Do nothing.
@@ -0,0 +1,166 @@
+//! Global Least-Laxity-First (G-LLF).
+//! The laxity of a node is `ref_absolute_deadline` - current time - (remaining execution time + the longest path
+//! from its successors to a sink). Each node keeps `latest_finish_time` = `ref_absolute_deadline` - the longest path
+//! from its successors, so that laxity = `latest_start_time` - current time, where
+//! `latest_start_time` = `latest_finish_time` - remaining execution time.
+//! On heterogeneous cores, the remaining execution time of a running node is its remaining time on its core, and that of
+//! a waiting node is its remaining time on the slowest core it may run on. The longest path from the successors is
+//! taken at speed 1.0.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use crate::dag_set_scheduler_common;
+use crate::log::DAGSetSchedulerLog;
+use crate::processor::homogeneous::HomogeneousProcessor;
+use crate::processor::processor_interface::Processor;
+use crate::task::dag::{Node, DAG};
+use petgraph::{algo::toposort, graph::Graph};
+
+use super::dag_set_scheduler::{DAGSetSchedulerBase, SchedulerConfig};
+use super::proposed_edf_scheduler::set_ref_absolute_deadlines;
+
+/// Assign `latest_finish_time` and `latest_start_time` to all nodes. `ref_absolute_deadline` must be assigned.
+pub fn set_latest_start_times(dag: &mut Graph<Node, i32>) {
+    let mut suc_critical_path_lengths = vec![0; dag.node_count()];
+    for node_i in toposort(&*dag, None)
+        .expect("The graph is not a DAG.")
+        .into_iter()
+        .rev()
+    {
+        suc_critical_path_lengths[node_i.index()] = dag
+            .get_suc(node_i)
+            .iter()
+            .map(|suc_i| {
+                dag[*suc_i].get_value("execution_time") + suc_critical_path_lengths[suc_i.index()]
+            })
+            .max()
+            .unwrap_or(0);
+    }
+
+    for node_i in dag.node_indices() {
+        let latest_finish_time = dag[node_i].get_value("ref_absolute_deadline")
+            - suc_critical_path_lengths[node_i.index()];
+        dag.set_param(node_i, "latest_finish_time", latest_finish_time);
+        dag.set_param(
+            node_i,
+            "latest_start_time",
+            latest_finish_time - dag[node_i].get_value("execution_time"),
+        );
+    }
+}
+
+/// `latest_start_time` of a waiting node, taking its remaining work at the slowest core it may run on. The execution
+/// time of a preempted node is its remaining work.
+pub fn get_latest_start_time(node: &Node, processor: &impl Processor) -> i32 {
+    let core_speeds = processor.get_core_speeds();
+    let slowest_speed = (0..processor.get_num_cores())
+        .filter(|&core_i| processor.is_allowed_core(core_i, node))
+        .map(|core_i| core_speeds[core_i])
+        .reduce(f64::min)
+        .unwrap_or_else(|| core_speeds.iter().copied().fold(f64::INFINITY, f64::min));
+    let remain_proc_time = (node.get_value("execution_time") as f64 / slowest_speed).ceil() as i32;
+    node.get_value("latest_finish_time") - remain_proc_time
+}
+
+pub struct GlobalLLFScheduler<T: Processor + Clone = HomogeneousProcessor> {
+    dag_set: Vec<Graph<Node, i32>>,
+    processor: T,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    config: SchedulerConfig,
+}
+
+impl<T: Processor + Clone> DAGSetSchedulerBase<T> for GlobalLLFScheduler<T> {
+    dag_set_scheduler_common!(T);
+
+    fn update_params_when_release(dag: &mut Graph<Node, i32>, _job_id: i32) {
+        set_ref_absolute_deadlines(dag);
+        set_latest_start_times(dag);
+    }
+
+    fn update_params_before_allocation(&mut self, ready_queue: &mut VecDeque<Node>) {
+        for node in ready_queue.iter_mut() {
+            let latest_start_time = get_latest_start_time(node, &self.processor);
+            node.params
+                .insert("latest_start_time".to_string(), latest_start_time);
+        }
+        // The laxity of a running node does not decrease, so its latest start time moves forward.
+        self.processor
+            .update_processing_nodes(|node, remain_proc_time| {
+                let latest_start_time = node.get_value("latest_finish_time") - remain_proc_time;
+                node.params
+                    .insert("latest_start_time".to_string(), latest_start_time);
+            });
+    }
+
+    fn get_next_decision_time(&self, ready_queue: &VecDeque<Node>) -> Option<i32> {
+        // A waiting node preempts once its laxity gets smaller than that of the running node with the largest laxity.
+        // Two nodes of equal laxity then preempt each other at every time unit, which makes G-LLF slow to simulate.
+        // Saturated, as a resource protocol may raise the latest start time of a running node to near i32::MIN.
+        ready_queue
+            .iter()
+            .filter_map(|node| {
+                let (max_latest_start_time, _) = self
+                    .processor
+                    .get_max_and_index("latest_start_time", node)?;
+                let latest_start_time = get_latest_start_time(node, &self.processor);
+                (latest_start_time >= max_latest_start_time).then_some(
+                    self.current_time
+                        .saturating_add(latest_start_time.saturating_sub(max_latest_start_time))
+                        .saturating_add(1),
+                )
+            })
+            .min()
+    }
+
+    fn sort_ready_queue(&self, ready_queue: &mut VecDeque<Node>) {
+        ready_queue.make_contiguous().sort_by(|a, b| {
+            match get_latest_start_time(a, &self.processor)
+                .cmp(&get_latest_start_time(b, &self.processor))
+            {
+                // If the laxities are equal, compare by ref_absolute_deadline, id and dag_id
+                Ordering::Equal => a
+                    .get_value("ref_absolute_deadline")
+                    .cmp(&b.get_value("ref_absolute_deadline"))
+                    .then(a.id.cmp(&b.id))
+                    .then(a.get_value("dag_id").cmp(&b.get_value("dag_id"))),
+                other => other,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests_llf_scheduler {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i32, execution_time: i32) -> Node {
+        Node::new(
+            id,
+            BTreeMap::from([("execution_time".to_string(), execution_time)]),
+        )
+    }
+
+    #[test]
+    fn test_set_latest_start_times() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(create_node(0, 1));
+        let n1 = dag.add_node(create_node(1, 4));
+        let n2 = dag.add_node(create_node(2, 2));
+        let n3 = dag.add_node(create_node(3, 1));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_edge(n1, n3, 0);
+        dag.add_edge(n2, n3, 0);
+        dag.set_param_to_all_nodes("ref_absolute_deadline", 10);
+
+        set_latest_start_times(&mut dag);
+        let latest_start_times = dag
+            .node_weights()
+            .map(|node| node.get_value("latest_start_time"))
+            .collect::<Vec<_>>();
+        assert_eq!(latest_start_times, vec![4, 5, 7, 9]);
+    }
+}
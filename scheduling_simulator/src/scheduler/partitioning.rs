@@ -0,0 +1,285 @@
+//! Static partitioning of a DAGSet onto cores by bin-packing heuristics.
+//! Pinning every node to a single core through its `partitioned_core` turns the global schedulers into their
+//! partitioned counterparts. The ready queue stays shared, but it is sorted by priority and a node is only allocated
+//! to or preempts its own core, so each core runs the highest-priority ready node pinned to it, as with a per-core
+//! ready queue.
+//! The capacity of each core is its speed, so a core at speed 0.5 accepts items of up to half the utilization.
+//! A node with an `affinity` is only mapped to one of the cores in it. Partitioning a DAGSet again replaces the
+//! previous mapping.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use clap::ValueEnum;
+use petgraph::graph::{Graph, NodeIndex};
+
+use crate::task::dag::{Node, DAG};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum BinPackingHeuristic {
+    /// First-fit decreasing
+    Ffd,
+    /// Best-fit decreasing
+    Bfd,
+    /// Worst-fit decreasing
+    Wfd,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum PartitioningUnit {
+    /// Each node is mapped to a core separately.
+    Node,
+    /// All nodes of a DAG are mapped to the same core.
+    Dag,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitioningError {
+    pub dag_id: i32,
+    pub node_id: Option<i32>,
+    pub utilization: f32,
+}
+
+impl fmt::Display for PartitioningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.node_id {
+            Some(node_id) => write!(
+                f,
+                "Node {} of DAG {} (utilization: {}) does not fit on any core.",
+                node_id, self.dag_id, self.utilization
+            ),
+            None => write!(
+                f,
+                "DAG {} (utilization: {}) does not fit on any core.",
+                self.dag_id, self.utilization
+            ),
+        }
+    }
+}
+
+/// Absorbs the rounding error of summing f32 utilizations.
+const CAPACITY_TOLERANCE: f32 = 1e-6;
+
+struct PartitioningItem {
+    dag_i: usize,
+    node_indices: Vec<NodeIndex>,
+    utilization: f32,
+    /// The cores allowed for all nodes of the item. None means all cores.
+    affinity: Option<BTreeSet<usize>>,
+}
+
+fn get_affinity(dag: &Graph<Node, i32>, node_indices: &[NodeIndex]) -> Option<BTreeSet<usize>> {
+    node_indices
+        .iter()
+        .filter_map(|&node_i| dag[node_i].affinity.as_ref())
+        .fold(None, |affinity, node_affinity| match affinity {
+            Some(affinity) => Some(&affinity & node_affinity),
+            None => Some(node_affinity.clone()),
+        })
+}
+
+fn create_items(dag_set: &[Graph<Node, i32>], unit: PartitioningUnit) -> Vec<PartitioningItem> {
+    let mut items = Vec::new();
+    for (dag_i, dag) in dag_set.iter().enumerate() {
+        let period = dag.get_dag_param("period") as f32;
+        match unit {
+            PartitioningUnit::Node => {
+                for node_i in dag.node_indices() {
+                    items.push(PartitioningItem {
+                        dag_i,
+                        node_indices: vec![node_i],
+                        utilization: dag[node_i].get_value("execution_time") as f32 / period,
+                        affinity: get_affinity(dag, &[node_i]),
+                    });
+                }
+            }
+            PartitioningUnit::Dag => {
+                let node_indices = dag.node_indices().collect::<Vec<_>>();
+                items.push(PartitioningItem {
+                    dag_i,
+                    affinity: get_affinity(dag, &node_indices),
+                    node_indices,
+                    utilization: dag.get_utilization(),
+                })
+            }
+        }
+    }
+
+    // The sort is stable, so items with the same utilization keep the order of dag_id and node id.
+    items.sort_by(|a, b| b.utilization.total_cmp(&a.utilization));
+    items
+}
+
+/// Best fit is the core with the least remaining capacity after the item, and worst fit the one with the most.
+pub(crate) fn choose_core(
+    core_utilizations: &[f32],
+    core_capacities: &[f32],
+    utilization: f32,
+    affinity: Option<&BTreeSet<usize>>,
+    heuristic: BinPackingHeuristic,
+) -> Option<usize> {
+    let mut fitting_cores = core_utilizations
+        .iter()
+        .zip(core_capacities)
+        .map(|(core_utilization, capacity)| capacity - core_utilization)
+        .enumerate()
+        .filter(|&(core_i, remaining_capacity)| {
+            affinity.is_none_or(|affinity| affinity.contains(&core_i))
+                && utilization <= remaining_capacity + CAPACITY_TOLERANCE
+        });
+
+    match heuristic {
+        BinPackingHeuristic::Ffd => fitting_cores.next(),
+        BinPackingHeuristic::Bfd => fitting_cores.min_by(|a, b| a.1.total_cmp(&b.1)),
+        // max_by returns the last of equal elements, so use min_by with a reversed order to prefer lower core indices.
+        BinPackingHeuristic::Wfd => fitting_cores.min_by(|a, b| b.1.total_cmp(&a.1)),
+    }
+    .map(|(core_i, _)| core_i)
+}
+
+/// Pin each node of `dag_set` to one of `num_cores` identical cores. See `partition_dag_set_with_speeds`.
+pub fn partition_dag_set(
+    dag_set: &mut [Graph<Node, i32>],
+    num_cores: usize,
+    heuristic: BinPackingHeuristic,
+    unit: PartitioningUnit,
+) -> Result<Vec<f32>, PartitioningError> {
+    partition_dag_set_with_speeds(dag_set, &vec![1.0; num_cores], heuristic, unit)
+}
+
+/// Pin each node of `dag_set` to one core by setting its `partitioned_core`. A core accepts items as long as its total
+/// utilization does not exceed its speed. Returns the utilization of each core.
+pub fn partition_dag_set_with_speeds(
+    dag_set: &mut [Graph<Node, i32>],
+    core_speeds: &[f64],
+    heuristic: BinPackingHeuristic,
+    unit: PartitioningUnit,
+) -> Result<Vec<f32>, PartitioningError> {
+    let core_capacities = core_speeds
+        .iter()
+        .map(|&speed| speed as f32)
+        .collect::<Vec<_>>();
+    let mut core_utilizations = vec![0.0; core_speeds.len()];
+    for item in create_items(dag_set, unit) {
+        let dag = &mut dag_set[item.dag_i];
+        let Some(core_i) = choose_core(
+            &core_utilizations,
+            &core_capacities,
+            item.utilization,
+            item.affinity.as_ref(),
+            heuristic,
+        ) else {
+            return Err(PartitioningError {
+                dag_id: dag.get_dag_param("dag_id"),
+                node_id: match unit {
+                    PartitioningUnit::Node => Some(dag[item.node_indices[0]].id),
+                    PartitioningUnit::Dag => None,
+                },
+                utilization: item.utilization,
+            });
+        };
+
+        core_utilizations[core_i] += item.utilization;
+        for node_i in item.node_indices {
+            dag.set_param(node_i, "partitioned_core", core_i as i32);
+        }
+    }
+
+    Ok(core_utilizations)
+}
+
+#[cfg(test)]
+mod tests_partitioning {
+    use super::*;
+
+    #[test]
+    fn test_choose_core() {
+        let core_utilizations = [0.5, 0.7, 0.2, 0.7];
+        let core_capacities = [1.0; 4];
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                None,
+                BinPackingHeuristic::Ffd
+            ),
+            Some(0)
+        );
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                None,
+                BinPackingHeuristic::Bfd
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                None,
+                BinPackingHeuristic::Wfd
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.9,
+                None,
+                BinPackingHeuristic::Ffd
+            ),
+            None
+        );
+        // Only cores 1 and 3 are allowed.
+        let affinity = BTreeSet::from([1, 3]);
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                Some(&affinity),
+                BinPackingHeuristic::Ffd
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                Some(&affinity),
+                BinPackingHeuristic::Wfd
+            ),
+            Some(1)
+        );
+
+        // Core 2 at speed 0.4 only has 0.2 left, so worst fit takes core 0, and an item of 0.2 fills core 2 best.
+        let core_capacities = [1.0, 1.0, 0.4, 1.0];
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.3,
+                None,
+                BinPackingHeuristic::Wfd
+            ),
+            Some(0)
+        );
+        assert_eq!(
+            choose_core(
+                &core_utilizations,
+                &core_capacities,
+                0.2,
+                None,
+                BinPackingHeuristic::Bfd
+            ),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! ROS 2 executors (rclcpp SingleThreadedExecutor and MultiThreadedExecutor).
+//! Each node is a callback registered to the executor given by its `executor` parameter (0 by default), and each
+//! executor owns a disjoint set of cores as its threads. A thread never preempts the callback it is running.
+//!
+//! An executor does not see the ready callbacks directly. When it has an idle thread and has taken everything from its
+//! last wait-set snapshot, it takes a new snapshot of the callbacks with pending work, and the threads take callbacks
+//! from it in the order of timers, subscriptions and services, each in the registration order (dag_id, node id).
+//! A callback appears once per snapshot, so the pending jobs of the same callback are taken one per snapshot, and a
+//! callback that becomes ready after the snapshot waits for the next one even if it has a higher priority.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use crate::dag_set_scheduler_common;
+use crate::log::DAGSetSchedulerLog;
+use crate::processor::homogeneous::HomogeneousProcessor;
+use crate::processor::processor_interface::Processor;
+use crate::task::callback::CallbackType;
+use crate::task::dag::{Node, DAG};
+use petgraph::graph::Graph;
+
+use super::dag_set_scheduler::{DAGSetSchedulerBase, SchedulerConfig};
+
+/// `snapshot_index` of a callback that is not in the snapshot of its executor, which may not run on any core.
+const NOT_IN_SNAPSHOT: i32 = i32::MAX;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutorConfigError {
+    TooManyThreads {
+        num_threads: usize,
+        num_cores: usize,
+    },
+    UnknownExecutor {
+        dag_id: i32,
+        node_id: i32,
+        executor_i: i32,
+    },
+    /// The executor has no thread, or the `affinity` of the node does not include any of its threads.
+    AffinityConflict {
+        dag_id: i32,
+        node_id: i32,
+        executor_i: i32,
+    },
+}
+
+impl fmt::Display for ExecutorConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecutorConfigError::TooManyThreads {
+                num_threads,
+                num_cores,
+            } => write!(
+                f,
+                "The executors have {} threads in total, but only {} cores are available.",
+                num_threads, num_cores
+            ),
+            ExecutorConfigError::UnknownExecutor {
+                dag_id,
+                node_id,
+                executor_i,
+            } => write!(
+                f,
+                "Executor {} of node {} of DAG {} does not exist.",
+                executor_i, node_id, dag_id
+            ),
+            ExecutorConfigError::AffinityConflict {
+                dag_id,
+                node_id,
+                executor_i,
+            } => write!(
+                f,
+                "The affinity of node {} of DAG {} excludes all threads of executor {}.",
+                node_id, dag_id, executor_i
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Executor {
+    /// The cores that run the threads of the executor.
+    cores: BTreeSet<usize>,
+    /// (dag_id, job_id, node id) of the callbacks that have not been taken from the snapshot yet, in the order taken.
+    snapshot: Vec<(i32, i32, i32)>,
+}
+
+fn get_callback_key(node: &Node) -> (i32, i32, i32) {
+    (node.get_value("dag_id"), node.get_value("job_id"), node.id)
+}
+
+pub struct ROS2ExecutorScheduler<T: Processor + Clone = HomogeneousProcessor> {
+    dag_set: Vec<Graph<Node, i32>>,
+    processor: T,
+    log: DAGSetSchedulerLog,
+    current_time: i32,
+    config: SchedulerConfig,
+    executors: Vec<Executor>,
+}
+
+impl<T: Processor + Clone> ROS2ExecutorScheduler<T> {
+    /// `num_threads[i]` is the number of threads of executor i, which are bound to consecutive cores.
+    /// A single-threaded executor has 1 thread.
+    pub fn try_new_with_executors(
+        dag_set: &[Graph<Node, i32>],
+        processor: &T,
+        num_threads: &[usize],
+    ) -> Result<Self, ExecutorConfigError> {
+        let num_cores = processor.get_num_cores();
+        if num_threads.iter().sum::<usize>() > num_cores {
+            return Err(ExecutorConfigError::TooManyThreads {
+                num_threads: num_threads.iter().sum::<usize>(),
+                num_cores,
+            });
+        }
+        let mut next_core_i = 0;
+        let executors = num_threads
+            .iter()
+            .map(|&num_threads_i| {
+                let cores = (next_core_i..next_core_i + num_threads_i).collect();
+                next_core_i += num_threads_i;
+                Executor {
+                    cores,
+                    snapshot: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut dag_set = dag_set.to_vec();
+        for dag in dag_set.iter_mut() {
+            let source_i = dag.get_source();
+            for node_i in dag.node_indices() {
+                let node = &mut dag[node_i];
+                let executor_i = node.get_value_or("executor", 0);
+                let Some(executor) = usize::try_from(executor_i)
+                    .ok()
+                    .and_then(|executor_i| executors.get(executor_i))
+                else {
+                    return Err(ExecutorConfigError::UnknownExecutor {
+                        dag_id: node.get_value("dag_id"),
+                        node_id: node.id,
+                        executor_i,
+                    });
+                };
+                // The callback only runs on the threads of its executor within its own affinity.
+                let executor_affinity = match &node.affinity {
+                    Some(affinity) => &executor.cores & affinity,
+                    None => executor.cores.clone(),
+                };
+                if executor_affinity.is_empty() {
+                    return Err(ExecutorConfigError::AffinityConflict {
+                        dag_id: node.get_value("dag_id"),
+                        node_id: node.id,
+                        executor_i,
+                    });
+                }
+                // A source node is triggered by the timer of its period, and the others by the output of their
+                // predecessors.
+                let default_callback_type = if node_i == source_i {
+                    CallbackType::Timer
+                } else {
+                    CallbackType::Subscription
+                };
+                let callback_type =
+                    node.get_value_or("callback_type", default_callback_type as i32);
+                node.params.insert("executor".to_string(), executor_i);
+                node.affinity = Some(executor_affinity);
+                node.params
+                    .insert("callback_type".to_string(), callback_type);
+                node.params
+                    .insert("snapshot_index".to_string(), NOT_IN_SNAPSHOT);
+            }
+        }
+
+        Ok(Self {
+            log: DAGSetSchedulerLog::new(&dag_set, num_cores),
+            dag_set,
+            processor: processor.clone(),
+            current_time: 0,
+            config: Default::default(),
+            executors,
+        })
+    }
+
+    fn has_idle_thread(&self, executor: &Executor) -> bool {
+        executor
+            .cores
+            .iter()
+            .any(|&core_i| self.processor.get_processing_node(core_i).is_none())
+    }
+
+    /// Take a new snapshot of the callbacks with pending work in the ready queue.
+    fn take_snapshot(executor_i: i32, ready_queue: &VecDeque<Node>) -> Vec<(i32, i32, i32)> {
+        let mut pending_callbacks = ready_queue
+            .iter()
+            .filter(|node| node.get_value("executor") == executor_i)
+            .map(|node| {
+                (
+                    node.get_value("callback_type"),
+                    node.get_value("dag_id"),
+                    node.id,
+                    node.get_value("job_id"),
+                )
+            })
+            .collect::<Vec<_>>();
+        pending_callbacks.sort();
+        // The oldest job of each callback
+        pending_callbacks.dedup_by_key(|&mut (_, dag_id, node_id, _)| (dag_id, node_id));
+        pending_callbacks
+            .into_iter()
+            .map(|(_, dag_id, node_id, job_id)| (dag_id, job_id, node_id))
+            .collect()
+    }
+}
+
+impl<T: Processor + Clone> DAGSetSchedulerBase<T> for ROS2ExecutorScheduler<T> {
+    dag_set_scheduler_common!(T, accessors);
+
+    /// A single multi-threaded executor with a thread on each core.
+    fn new(dag_set: &[Graph<Node, i32>], processor: &T) -> Self {
+        Self::try_new_with_executors(dag_set, processor, &[processor.get_num_cores()])
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn update_params_when_release(_dag: &mut Graph<Node, i32>, _job_id: i32) {
+        // Do nothing.
+    }
+
+    fn update_params_before_allocation(&mut self, ready_queue: &mut VecDeque<Node>) {
+        let mut executors = std::mem::take(&mut self.executors);
+        for (executor_i, executor) in executors.iter_mut().enumerate() {
+            // Callbacks that are no longer in the ready queue have been taken by a thread.
+            executor.snapshot.retain(|&callback| {
+                ready_queue
+                    .iter()
+                    .any(|node| get_callback_key(node) == callback)
+            });
+            if executor.snapshot.is_empty() && self.has_idle_thread(executor) {
+                executor.snapshot = Self::take_snapshot(executor_i as i32, ready_queue);
+            }
+        }
+
+        for node in ready_queue.iter_mut() {
+            let executor = &executors[node.get_value("executor") as usize];
+            let snapshot_i = executor
+                .snapshot
+                .iter()
+                .position(|&callback| callback == get_callback_key(node));
+            node.params.insert(
+                "snapshot_index".to_string(),
+                snapshot_i.map_or(NOT_IN_SNAPSHOT, |snapshot_i| snapshot_i as i32),
+            );
+        }
+        self.executors = executors;
+    }
+
+    fn is_allocatable(&self, node: &Node) -> bool {
+        node.get_value("snapshot_index") != NOT_IN_SNAPSHOT
+    }
+
+    fn sort_ready_queue(&self, ready_queue: &mut VecDeque<Node>) {
+        ready_queue.make_contiguous().sort_by(|a, b| {
+            match a
+                .get_value("snapshot_index")
+                .cmp(&b.get_value("snapshot_index"))
+            {
+                Ordering::Equal => get_callback_key(a).cmp(&get_callback_key(b)),
+                other => other,
+            }
+        });
+    }
+}
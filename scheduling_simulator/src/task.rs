@@ -1,3 +1,7 @@
+pub mod arrival;
+pub mod callback;
+pub mod conditional;
 pub mod dag;
 pub mod dag_creator;
+pub mod dag_generator;
 pub mod dag_set;
@@ -0,0 +1,180 @@
+//! Sporadic arrivals of DAG jobs.
+//! The first job of a DAG arrives at its optional `offset` parameter (0 by default).
+//! `period` is the minimum inter-arrival time. Two optional DAG parameters, given next to `period` in the YAML,
+//! make the releases sporadic:
+//! - `inter_arrival_slack`: a random time in [0, inter_arrival_slack] is added to each inter-arrival time.
+//! - `release_jitter`: a job becomes ready a random time in [0, release_jitter] after it arrives.
+//!
+//! Deadlines and response times are measured from the arrival time, so the jitter consumes the deadline.
+//! The random times of each job are drawn from an RNG seeded by (seed, dag_id, job_id), so every scheduler sees the
+//! same arrivals regardless of when it asks for them.
+
+use petgraph::Graph;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::dag::{Node, DAG};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JobArrival {
+    pub arrival_time: i32,
+    /// The time at which the source node becomes ready.
+    pub release_time: i32,
+}
+
+/// SplitMix64 finalizer, so that nearby inputs give unrelated seeds.
+pub(crate) fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+/// Seed of run `run_i`. Runs of nearby base seeds share no seeds, unlike `base_seed + run_i`.
+pub fn derive_run_seed(base_seed: u64, run_i: u64) -> u64 {
+    mix(base_seed ^ mix(run_i))
+}
+
+pub(crate) fn derive_job_seed(seed: u64, dag_id: i32, job_id: i32) -> u64 {
+    mix(mix(mix(seed) ^ dag_id as u64) ^ job_id as u64)
+}
+
+/// Returns the arrival of job `job_id`, given the arrival of the previous job.
+/// Jobs of the same DAG are released in order even if the jitter of a job is larger than the inter-arrival time.
+/// Times beyond `i32::MAX` are saturated to it, so such a job is never released.
+pub fn get_job_arrival(
+    dag: &Graph<Node, i32>,
+    seed: u64,
+    job_id: i32,
+    prev_arrival: Option<&JobArrival>,
+) -> JobArrival {
+    let mut rng = StdRng::seed_from_u64(derive_job_seed(seed, dag.get_dag_param("dag_id"), job_id));
+    let inter_arrival_slack = dag.get_dag_param_or("inter_arrival_slack", 0);
+    let release_jitter = dag.get_dag_param_or("release_jitter", 0);
+
+    let arrival_time = match prev_arrival {
+        Some(prev_arrival) => prev_arrival
+            .arrival_time
+            .saturating_add(dag.get_dag_param("period"))
+            .saturating_add(rng.gen_range(0..=inter_arrival_slack)),
+        None => dag.get_dag_param_or("offset", 0),
+    };
+    let release_time = arrival_time.saturating_add(rng.gen_range(0..=release_jitter));
+
+    JobArrival {
+        arrival_time,
+        release_time: prev_arrival.map_or(release_time, |prev_arrival| {
+            release_time.max(prev_arrival.release_time)
+        }),
+    }
+}
+
+/// Overwrite the `offset` of each DAG with a random time in [0, period).
+pub fn randomize_offsets(dag_set: &mut [Graph<Node, i32>], seed: u64) {
+    let mut rng = StdRng::seed_from_u64(mix(seed));
+    for dag in dag_set.iter_mut() {
+        let offset = rng.gen_range(0..dag.get_dag_param("period"));
+        dag.set_param_to_all_nodes("offset", offset);
+    }
+}
+
+#[cfg(test)]
+mod tests_arrival {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_dag(params: &[(&str, i32)]) -> Graph<Node, i32> {
+        let mut dag = Graph::<Node, i32>::new();
+        let mut params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect::<BTreeMap<_, _>>();
+        params.insert("dag_id".to_string(), 0);
+        dag.add_node(Node::new(0, params));
+        dag
+    }
+
+    fn get_arrivals(dag: &Graph<Node, i32>, seed: u64, num_jobs: i32) -> Vec<JobArrival> {
+        let mut arrivals: Vec<JobArrival> = Vec::new();
+        for job_id in 0..num_jobs {
+            let arrival = get_job_arrival(dag, seed, job_id, arrivals.last());
+            arrivals.push(arrival);
+        }
+        arrivals
+    }
+
+    #[test]
+    fn test_get_job_arrival_periodic() {
+        let dag = create_dag(&[("period", 10)]);
+        let release_times = get_arrivals(&dag, 0, 4)
+            .iter()
+            .map(|arrival| (arrival.arrival_time, arrival.release_time))
+            .collect::<Vec<_>>();
+        assert_eq!(release_times, vec![(0, 0), (10, 10), (20, 20), (30, 30)]);
+    }
+
+    #[test]
+    fn test_get_job_arrival_offset() {
+        let dag = create_dag(&[("period", 10), ("offset", 3)]);
+        let arrival_times = get_arrivals(&dag, 0, 3)
+            .iter()
+            .map(|arrival| arrival.arrival_time)
+            .collect::<Vec<_>>();
+        assert_eq!(arrival_times, vec![3, 13, 23]);
+    }
+
+    #[test]
+    fn test_randomize_offsets() {
+        let get_random_offsets = |seed| {
+            let mut dag_set = vec![create_dag(&[("period", 10)]); 20];
+            randomize_offsets(&mut dag_set, seed);
+            dag_set
+                .iter()
+                .map(|dag| dag.get_dag_param("offset"))
+                .collect::<Vec<_>>()
+        };
+        let offsets = get_random_offsets(0);
+        assert!(offsets.iter().all(|offset| (0..10).contains(offset)));
+        assert!(offsets.iter().any(|&offset| offset != offsets[0]));
+        assert_eq!(offsets, get_random_offsets(0));
+    }
+
+    #[test]
+    fn test_get_job_arrival_sporadic() {
+        let dag = create_dag(&[
+            ("period", 10),
+            ("inter_arrival_slack", 5),
+            ("release_jitter", 3),
+        ]);
+        let arrivals = get_arrivals(&dag, 1, 100);
+        for window in arrivals.windows(2) {
+            let inter_arrival_time = window[1].arrival_time - window[0].arrival_time;
+            assert!((10..=15).contains(&inter_arrival_time));
+        }
+        for arrival in arrivals.iter() {
+            assert!((0..=3).contains(&(arrival.release_time - arrival.arrival_time)));
+        }
+        assert_eq!(arrivals, get_arrivals(&dag, 1, 100));
+        assert_ne!(arrivals, get_arrivals(&dag, 2, 100));
+    }
+
+    #[test]
+    fn test_get_job_arrival_saturates() {
+        let dag = create_dag(&[
+            ("period", i32::MAX - 1),
+            ("inter_arrival_slack", 10),
+            ("release_jitter", 10),
+        ]);
+        let arrivals = get_arrivals(&dag, 0, 3);
+        assert_eq!(arrivals[2].arrival_time, i32::MAX);
+        assert_eq!(arrivals[2].release_time, i32::MAX);
+    }
+
+    #[test]
+    fn test_get_job_arrival_in_order() {
+        let dag = create_dag(&[("period", 2), ("release_jitter", 10)]);
+        let arrivals = get_arrivals(&dag, 0, 100);
+        for window in arrivals.windows(2) {
+            assert!(window[0].release_time <= window[1].release_time);
+        }
+    }
+}
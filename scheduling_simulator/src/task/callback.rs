@@ -0,0 +1,21 @@
+//! ROS 2 callback types of nodes, given as `callback_type` in the DAG YAML files and stored in the node parameters as
+//! the value of the variant.
+
+/// Declared in the order in which an executor takes the callbacks of a wait-set snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CallbackType {
+    Timer = 0,
+    Subscription = 1,
+    Service = 2,
+}
+
+impl CallbackType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "timer" => Some(CallbackType::Timer),
+            "subscription" => Some(CallbackType::Subscription),
+            "service" => Some(CallbackType::Service),
+            _ => None,
+        }
+    }
+}
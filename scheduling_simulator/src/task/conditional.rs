@@ -0,0 +1,132 @@
+//! Conditional DAGs (C-DAGs).
+//! The outgoing edges of a node with `conditional: 1` are alternatives, and exactly one of them is taken in each job.
+//! The branch is chosen when the conditional node completes, in one of two ways:
+//! - `branch_pattern.<i>`: the successor ids taken by consecutive jobs, repeated cyclically.
+//! - `branch_probability.<successor id>`: the probability of each successor in units of `1 / PROBABILITY_SCALE`.
+//!   The successors are equally likely if no probability is given.
+//!
+//! A node whose incoming edges are all not taken is skipped, together with the nodes that only it leads to.
+//! The random choice of each job is drawn from an RNG seeded by (seed, dag_id, job_id, node id), like job arrivals.
+
+use petgraph::graph::{Graph, NodeIndex};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    arrival::{derive_job_seed, mix},
+    dag::{Node, DAG},
+};
+
+pub const PROBABILITY_SCALE: i32 = 1_000_000;
+
+pub fn get_branch_probability_key(suc_id: i32) -> String {
+    format!("branch_probability.{}", suc_id)
+}
+
+pub fn get_branch_pattern(node: &Node) -> Vec<i32> {
+    (0..)
+        .map_while(|i| node.params.get(&format!("branch_pattern.{}", i)).copied())
+        .collect()
+}
+
+/// Returns the successor taken by the current job of the conditional node.
+pub fn choose_branch(dag: &Graph<Node, i32>, node_i: NodeIndex, seed: u64) -> NodeIndex {
+    let node = &dag[node_i];
+    let mut suc_nodes = dag.get_suc(node_i);
+    suc_nodes.sort_by_key(|&suc_i| dag[suc_i].id);
+    let job_id = node.get_value("job_id");
+
+    let branch_pattern = get_branch_pattern(node);
+    if !branch_pattern.is_empty() {
+        let suc_id = branch_pattern[job_id as usize % branch_pattern.len()];
+        return *suc_nodes
+            .iter()
+            .find(|&&suc_i| dag[suc_i].id == suc_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Node {} in the branch pattern of node {} is not its successor.",
+                    suc_id, node.id
+                )
+            });
+    }
+
+    let mut weights = suc_nodes
+        .iter()
+        .map(|&suc_i| node.get_value_or(&get_branch_probability_key(dag[suc_i].id), 0) as i64)
+        .collect::<Vec<_>>();
+    if weights.iter().all(|&weight| weight == 0) {
+        weights.fill(1);
+    }
+    let job_seed = derive_job_seed(seed, node.get_value("dag_id"), job_id);
+    let mut rng = StdRng::seed_from_u64(mix(job_seed ^ node.id as u64));
+    let mut sample = rng.gen_range(0..weights.iter().sum::<i64>());
+    for (&suc_i, weight) in suc_nodes.iter().zip(weights) {
+        if sample < weight {
+            return suc_i;
+        }
+        sample -= weight;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests_conditional {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_conditional_dag(params: &[(&str, i32)]) -> Graph<Node, i32> {
+        let mut dag = Graph::<Node, i32>::new();
+        let mut source_params = BTreeMap::from([
+            ("conditional".to_string(), 1),
+            ("dag_id".to_string(), 0),
+            ("job_id".to_string(), 0),
+        ]);
+        for &(key, value) in params {
+            source_params.insert(key.to_string(), value);
+        }
+        let n0 = dag.add_node(Node::new(0, source_params));
+        let n1 = dag.add_node(Node::new(1, BTreeMap::new()));
+        let n2 = dag.add_node(Node::new(2, BTreeMap::new()));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag
+    }
+
+    fn choose_branch_ids(dag: &mut Graph<Node, i32>, num_jobs: i32) -> Vec<i32> {
+        let source_i = dag.get_source();
+        (0..num_jobs)
+            .map(|job_id| {
+                dag.set_param(source_i, "job_id", job_id);
+                dag[choose_branch(dag, source_i, 0)].id
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_choose_branch_pattern() {
+        let mut dag = create_conditional_dag(&[
+            ("branch_pattern.0", 2),
+            ("branch_pattern.1", 1),
+            ("branch_pattern.2", 1),
+        ]);
+        assert_eq!(choose_branch_ids(&mut dag, 5), vec![2, 1, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_choose_branch_probability() {
+        let mut dag = create_conditional_dag(&[
+            ("branch_probability.1", PROBABILITY_SCALE),
+            ("branch_probability.2", 0),
+        ]);
+        assert_eq!(choose_branch_ids(&mut dag, 3), vec![1, 1, 1]);
+
+        let mut dag = create_conditional_dag(&[
+            ("branch_probability.1", PROBABILITY_SCALE / 4),
+            ("branch_probability.2", PROBABILITY_SCALE * 3 / 4),
+        ]);
+        let branch_ids = choose_branch_ids(&mut dag, 1000);
+        let num_branch2 = branch_ids.iter().filter(|&&id| id == 2).count();
+        assert!((700..800).contains(&num_branch2));
+        // The same seed gives the same branches.
+        assert_eq!(choose_branch_ids(&mut dag, 1000), branch_ids);
+    }
+}
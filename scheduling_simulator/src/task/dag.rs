@@ -1,21 +1,28 @@
 use petgraph::{
+    algo::toposort,
     graph::{Graph, NodeIndex},
     visit::EdgeRef,
     Direction::{Incoming, Outgoing},
 };
 use std::cmp::Ord;
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 /// custom node data structure for dag nodes (petgraph)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Node {
     pub id: i32,
     pub params: BTreeMap<String, i32>,
+    /// The ids of the cores that the node may run on. None means all cores.
+    pub affinity: Option<BTreeSet<usize>>,
 }
 
 impl Node {
     pub fn new(id: i32, params: BTreeMap<String, i32>) -> Node {
-        Node { id, params }
+        Node {
+            id,
+            params,
+            affinity: None,
+        }
     }
 
     pub fn get_id(&self) -> NodeIndex {
@@ -28,6 +35,31 @@ impl Node {
             .get(key)
             .unwrap_or_else(|| panic!("The key {} not found.", key))
     }
+
+    pub fn get_value_or(&self, key: &str, default: i32) -> i32 {
+        self.params.get(key).copied().unwrap_or(default)
+    }
+
+    /// Critical sections given as `cs.<resource>: <length>`. They run back to back at the beginning of the node in the
+    /// order of resource names. Returns each resource with the executed time at which its critical section ends.
+    pub fn get_critical_sections(&self) -> Vec<(&str, i32)> {
+        let mut end = 0;
+        self.params
+            .range("cs.".to_string()..)
+            .take_while(|(key, _)| key.starts_with("cs."))
+            .map(|(key, &length)| {
+                end += length;
+                (&key["cs.".len()..], end)
+            })
+            .collect()
+    }
+
+    /// Executed times at which the node may be preempted, given as `preemption_point.<i>`.
+    pub fn get_preemption_points(&self) -> Vec<i32> {
+        (0..)
+            .map_while(|i| self.params.get(&format!("preemption_point.{}", i)).copied())
+            .collect()
+    }
 }
 
 pub trait DAG {
@@ -41,10 +73,22 @@ pub trait DAG {
     fn get_anc(&self, node_i: NodeIndex) -> Vec<NodeIndex>;
     fn get_des(&self, node_i: NodeIndex) -> Vec<NodeIndex>;
     fn get_dag_param(&self, key: &str) -> i32;
+    fn get_dag_param_or(&self, key: &str, default: i32) -> i32;
     fn set_param_to_all_nodes(&mut self, key: &str, value: i32);
     fn is_node_ready(&self, node_i: NodeIndex) -> bool;
+    fn is_conditional(&self, node_i: NodeIndex) -> bool;
+    fn is_edge_taken(&self, pre_i: NodeIndex, node_i: NodeIndex) -> bool;
     fn is_completed(&self) -> bool;
     fn get_utilization(&self) -> f32;
+    fn get_volume(&self) -> i32;
+    fn get_critical_path_length(&self) -> i32;
+    fn get_critical_path_length_with_delays(&self) -> i32;
+    fn get_earliest_start_times(&self) -> Vec<i32>;
+    fn get_latest_start_times(&self) -> Vec<i32>;
+    fn get_slacks(&self) -> Vec<i32>;
+    fn get_depth(&self) -> i32;
+    fn get_width(&self) -> usize;
+    fn get_parallelism_profile(&self) -> Vec<(i32, usize)>;
 }
 
 impl DAG for Graph<Node, i32> {
@@ -153,6 +197,10 @@ impl DAG for Graph<Node, i32> {
         self[self.get_source()].get_value(key)
     }
 
+    fn get_dag_param_or(&self, key: &str, default: i32) -> i32 {
+        self[self.get_source()].get_value_or(key, default)
+    }
+
     fn set_param_to_all_nodes(&mut self, key: &str, value: i32) {
         if self.node_indices().count() == 0 {
             panic!("No node exists.");
@@ -167,11 +215,25 @@ impl DAG for Graph<Node, i32> {
         }
     }
 
+    /// All predecessors have been resolved, i.e., they have completed or been skipped.
+    /// The node itself is skipped instead of executed if none of its incoming edges has been taken.
     fn is_node_ready(&self, node_i: NodeIndex) -> bool {
         let pre_nodes_count = self.get_pre(node_i).len() as i32;
         pre_nodes_count == self[node_i].get_value("pre_done_count")
     }
 
+    /// Only one of the outgoing edges of a conditional node, `chosen_branch`, is taken in each job.
+    fn is_conditional(&self, node_i: NodeIndex) -> bool {
+        self[node_i].get_value_or("conditional", 0) == 1
+    }
+
+    fn is_edge_taken(&self, pre_i: NodeIndex, node_i: NodeIndex) -> bool {
+        let pre_node = &self[pre_i];
+        pre_node.get_value_or("skipped", 0) == 0
+            && (!self.is_conditional(pre_i)
+                || pre_node.get_value("chosen_branch") == self[node_i].id)
+    }
+
     fn is_completed(&self) -> bool {
         self.node_weights().all(|node| {
             if let Some(&completed) = node.params.get("completed") {
@@ -190,6 +252,187 @@ impl DAG for Graph<Node, i32> {
 
         total_execution_time as f32 / self.get_dag_param("period") as f32
     }
+
+    fn get_volume(&self) -> i32 {
+        self.node_weights()
+            .map(|node| node.get_value("execution_time"))
+            .sum()
+    }
+
+    /// The longest path length in terms of execution times. The edge delays are not included.
+    fn get_critical_path_length(&self) -> i32 {
+        let mut longest_finish_times = vec![0; self.node_count()];
+        for node_i in toposort(self, None).expect("The graph is not a DAG.") {
+            let longest_pre_finish_time = self
+                .get_pre(node_i)
+                .iter()
+                .map(|pre_i| longest_finish_times[pre_i.index()])
+                .max()
+                .unwrap_or(0);
+            longest_finish_times[node_i.index()] =
+                longest_pre_finish_time + self[node_i].get_value("execution_time");
+        }
+
+        longest_finish_times.into_iter().max().unwrap_or(0)
+    }
+
+    /// The longest path length including the edge delays, i.e., the end-to-end latency with infinite cores.
+    fn get_critical_path_length_with_delays(&self) -> i32 {
+        self.get_earliest_start_times()
+            .into_iter()
+            .zip(self.node_weights())
+            .map(|(earliest_start_time, node)| {
+                earliest_start_time + node.get_value("execution_time")
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The earliest start time of each node relative to the start of the source, when the DAG has infinite cores.
+    /// Indexed by node index.
+    fn get_earliest_start_times(&self) -> Vec<i32> {
+        let mut earliest_start_times = vec![0; self.node_count()];
+        for node_i in toposort(self, None).expect("The graph is not a DAG.") {
+            earliest_start_times[node_i.index()] = self
+                .edges_directed(node_i, Incoming)
+                .map(|edge| {
+                    let pre_i = edge.source();
+                    earliest_start_times[pre_i.index()]
+                        + self[pre_i].get_value("execution_time")
+                        + edge.weight()
+                })
+                .max()
+                .unwrap_or(0);
+        }
+
+        earliest_start_times
+    }
+
+    /// The latest start time of each node that does not extend the critical path length including the edge delays.
+    /// Indexed by node index.
+    fn get_latest_start_times(&self) -> Vec<i32> {
+        let critical_path_length = self.get_critical_path_length_with_delays();
+        let mut latest_start_times = vec![0; self.node_count()];
+        for node_i in toposort(self, None)
+            .expect("The graph is not a DAG.")
+            .into_iter()
+            .rev()
+        {
+            let latest_finish_time = self
+                .edges_directed(node_i, Outgoing)
+                .map(|edge| latest_start_times[edge.target().index()] - edge.weight())
+                .min()
+                .unwrap_or(critical_path_length);
+            latest_start_times[node_i.index()] =
+                latest_finish_time - self[node_i].get_value("execution_time");
+        }
+
+        latest_start_times
+    }
+
+    /// The latest start time minus the earliest start time of each node. The nodes on the critical path have no slack.
+    fn get_slacks(&self) -> Vec<i32> {
+        self.get_latest_start_times()
+            .into_iter()
+            .zip(self.get_earliest_start_times())
+            .map(|(latest_start_time, earliest_start_time)| latest_start_time - earliest_start_time)
+            .collect()
+    }
+
+    /// The number of nodes on the longest path in terms of nodes.
+    fn get_depth(&self) -> i32 {
+        let mut depths = vec![0; self.node_count()];
+        for node_i in toposort(self, None).expect("The graph is not a DAG.") {
+            depths[node_i.index()] = self
+                .get_pre(node_i)
+                .into_iter()
+                .map(|pre_i| depths[pre_i.index()])
+                .max()
+                .unwrap_or(0)
+                + 1;
+        }
+
+        depths.into_iter().max().unwrap_or(0)
+    }
+
+    /// The size of the maximum antichain, i.e., the maximum number of nodes that may run in parallel.
+    /// By Dilworth's theorem, it is the number of nodes minus the maximum matching between the nodes and their
+    /// descendants.
+    fn get_width(&self) -> usize {
+        let descendants = self
+            .node_indices()
+            .map(|node_i| self.get_des(node_i))
+            .collect::<Vec<_>>();
+
+        // Kuhn's augmenting path algorithm
+        fn try_match(
+            node_i: usize,
+            descendants: &[Vec<NodeIndex>],
+            visited: &mut [bool],
+            matched_anc: &mut [Option<usize>],
+        ) -> bool {
+            for des_i in descendants[node_i].iter().map(|des_i| des_i.index()) {
+                if visited[des_i] {
+                    continue;
+                }
+                visited[des_i] = true;
+                if matched_anc[des_i].is_none()
+                    || try_match(
+                        matched_anc[des_i].unwrap(),
+                        descendants,
+                        visited,
+                        matched_anc,
+                    )
+                {
+                    matched_anc[des_i] = Some(node_i);
+                    return true;
+                }
+            }
+            false
+        }
+
+        let mut matched_anc = vec![None; self.node_count()];
+        let num_matches = (0..self.node_count())
+            .filter(|&node_i| {
+                let mut visited = vec![false; self.node_count()];
+                try_match(node_i, &descendants, &mut visited, &mut matched_anc)
+            })
+            .count();
+
+        self.node_count() - num_matches
+    }
+
+    /// The number of nodes running when every node starts at its earliest start time, as (time, number of nodes)
+    /// at each time when the number changes. The last step lasts until the critical path length.
+    fn get_parallelism_profile(&self) -> Vec<(i32, usize)> {
+        let mut changes = BTreeMap::<i32, i32>::new();
+        for (earliest_start_time, node) in self
+            .get_earliest_start_times()
+            .into_iter()
+            .zip(self.node_weights())
+        {
+            let execution_time = node.get_value("execution_time");
+            if execution_time > 0 {
+                *changes.entry(earliest_start_time).or_insert(0) += 1;
+                *changes
+                    .entry(earliest_start_time + execution_time)
+                    .or_insert(0) -= 1;
+            }
+        }
+
+        let mut profile = Vec::<(i32, usize)>::new();
+        let mut num_running_nodes = 0;
+        for (time, change) in changes {
+            num_running_nodes += change;
+            if profile.last().map(|&(_, num)| num) != Some(num_running_nodes as usize) {
+                profile.push((time, num_running_nodes as usize));
+            }
+        }
+        // The last change ends all nodes.
+        profile.pop();
+
+        profile
+    }
 }
 
 #[cfg(test)]
@@ -199,7 +442,7 @@ mod tests_dag {
     fn create_node(key: &str, value: Option<i32>) -> Node {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value.unwrap_or_default());
-        Node { id: 0, params }
+        Node::new(0, params)
     }
 
     #[test]
@@ -325,6 +568,31 @@ mod tests_dag {
         assert!(dag.is_node_ready(n1));
     }
 
+    #[test]
+    fn test_is_edge_taken() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(Node::new(0, BTreeMap::new()));
+        let n1 = dag.add_node(Node::new(1, BTreeMap::new()));
+        let n2 = dag.add_node(Node::new(2, BTreeMap::new()));
+        let n3 = dag.add_node(Node::new(3, BTreeMap::new()));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_edge(n1, n3, 0);
+        dag.add_edge(n2, n3, 0);
+        assert!(dag.is_edge_taken(n0, n1));
+        assert!(dag.is_edge_taken(n0, n2));
+
+        dag.set_param(n0, "conditional", 1);
+        dag.set_param(n0, "chosen_branch", 2);
+        assert!(dag.is_conditional(n0));
+        assert!(!dag.is_edge_taken(n0, n1));
+        assert!(dag.is_edge_taken(n0, n2));
+
+        dag.set_param(n1, "skipped", 1);
+        assert!(!dag.is_edge_taken(n1, n3));
+        assert!(dag.is_edge_taken(n2, n3));
+    }
+
     #[test]
     fn test_get_utilization() {
         let mut dag = Graph::<Node, i32>::new();
@@ -340,4 +608,92 @@ mod tests_dag {
 
         assert_eq!(dag.get_utilization(), 0.6);
     }
+
+    #[test]
+    fn test_get_volume_and_critical_path_length() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(create_node("execution_time", Some(1)));
+        let n1 = dag.add_node(create_node("execution_time", Some(4)));
+        let n2 = dag.add_node(create_node("execution_time", Some(2)));
+        let n3 = dag.add_node(create_node("execution_time", Some(1)));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_edge(n1, n3, 0);
+        dag.add_edge(n2, n3, 0);
+
+        assert_eq!(dag.get_volume(), 8);
+        assert_eq!(dag.get_critical_path_length(), 6);
+    }
+
+    #[test]
+    fn test_get_start_times_and_slacks() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(create_node("execution_time", Some(1)));
+        let n1 = dag.add_node(create_node("execution_time", Some(4)));
+        let n2 = dag.add_node(create_node("execution_time", Some(2)));
+        let n3 = dag.add_node(create_node("execution_time", Some(1)));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 0);
+        dag.add_edge(n2, n3, 0);
+
+        // The delay of 1 is on the path through n2, which is still shorter than the path through n1.
+        assert_eq!(dag.get_critical_path_length(), 6);
+        assert_eq!(dag.get_critical_path_length_with_delays(), 6);
+        assert_eq!(dag.get_earliest_start_times(), vec![0, 1, 2, 5]);
+        assert_eq!(dag.get_latest_start_times(), vec![0, 1, 3, 5]);
+        assert_eq!(dag.get_slacks(), vec![0, 0, 1, 0]);
+        assert_eq!(dag.get_parallelism_profile(), vec![(0, 1), (2, 2), (4, 1)]);
+    }
+
+    #[test]
+    fn test_get_critical_path_length_with_delays() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(create_node("execution_time", Some(1)));
+        let n1 = dag.add_node(create_node("execution_time", Some(1)));
+        dag.add_edge(n0, n1, 10);
+
+        assert_eq!(dag.get_critical_path_length(), 2);
+        assert_eq!(dag.get_critical_path_length_with_delays(), 12);
+        assert_eq!(dag.get_slacks(), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_get_depth_and_width() {
+        let mut dag = Graph::<Node, i32>::new();
+        let n0 = dag.add_node(create_node("execution_time", None));
+        assert_eq!(dag.get_depth(), 1);
+        assert_eq!(dag.get_width(), 1);
+
+        let n1 = dag.add_node(create_node("execution_time", None));
+        let n2 = dag.add_node(create_node("execution_time", None));
+        let n3 = dag.add_node(create_node("execution_time", None));
+        let n4 = dag.add_node(create_node("execution_time", None));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_edge(n0, n3, 0);
+        dag.add_edge(n1, n4, 0);
+        dag.add_edge(n2, n4, 0);
+
+        assert_eq!(dag.get_depth(), 3);
+        // n1, n2 and n3 may run in parallel.
+        assert_eq!(dag.get_width(), 3);
+    }
+
+    #[test]
+    fn test_get_critical_sections() {
+        let node = Node::new(
+            0,
+            BTreeMap::from([
+                ("execution_time".to_string(), 10),
+                ("cs.tf_buffer".to_string(), 2),
+                ("cs.map".to_string(), 3),
+                ("period".to_string(), 100),
+            ]),
+        );
+        assert_eq!(
+            node.get_critical_sections(),
+            vec![("map", 3), ("tf_buffer", 5)]
+        );
+    }
 }
@@ -1,16 +1,21 @@
-//! Generate a petgraph DAG object from a yaml file
+//! Generate a petgraph DAG object from a yaml file, and write one back to a yaml file
 
 use petgraph::{graph::Graph, prelude::*};
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde_yaml::{Mapping, Value};
 use std::{
     collections::BTreeMap,
     fs::{self, File},
     io::{BufRead, BufReader},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 use yaml_rust::{Yaml, YamlLoader};
 
-use super::dag::{Node, DAG};
+use super::{
+    callback::CallbackType,
+    conditional::{get_branch_pattern, get_branch_probability_key, PROBABILITY_SCALE},
+    dag::{Node, DAG},
+};
 
 fn load_yaml(path: &str) -> Vec<Yaml> {
     if !path.ends_with(".yaml") && !path.ends_with(".yml") {
@@ -19,10 +24,33 @@ fn load_yaml(path: &str) -> Vec<Yaml> {
     YamlLoader::load_from_str(&fs::read_to_string(path).unwrap()).unwrap()
 }
 
-const AUTOWARE_ET_DIR: &str = "/home/atsushi/2024_RTSS_WiP_Evaluation/autoware_execution_times";
+/// Absolute paths are used as is. For relative paths, the file name is looked up in `et_dir_path` if given, so that
+/// any directory can hold the files, otherwise the path is resolved against the directory containing the DAG YAML file.
+fn resolve_execution_time_file(
+    yaml_path: &str,
+    et_dir_path: Option<&str>,
+    execution_time_file: &str,
+) -> PathBuf {
+    let execution_time_file = Path::new(execution_time_file);
+    if execution_time_file.is_absolute() {
+        return execution_time_file.to_path_buf();
+    }
 
-fn choice_execution_time_us(execution_time_file: &str) -> i32 {
-    let file = File::open(PathBuf::from(AUTOWARE_ET_DIR).join(execution_time_file)).unwrap();
+    match et_dir_path {
+        Some(et_dir_path) => PathBuf::from(et_dir_path).join(
+            execution_time_file
+                .file_name()
+                .unwrap_or_else(|| panic!("No file name in {}", execution_time_file.display())),
+        ),
+        None => Path::new(yaml_path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(execution_time_file),
+    }
+}
+
+fn choice_execution_time_us(execution_time_file: &Path, rng: &mut impl Rng) -> i32 {
+    let file = File::open(execution_time_file).unwrap();
     let reader = BufReader::new(file);
     let mut execution_times: Vec<i32> = Vec::new();
 
@@ -31,10 +59,14 @@ fn choice_execution_time_us(execution_time_file: &str) -> i32 {
     }
 
     // ns -> us
-    (*execution_times.choose(&mut rand::thread_rng()).unwrap() as f32 / 1000.0).ceil() as i32
+    (*execution_times.choose(rng).unwrap() as f32 / 1000.0).ceil() as i32
 }
 
-fn create_dag_from_yaml(path: &str) -> Graph<Node, i32> {
+fn create_dag_from_yaml(
+    path: &str,
+    et_dir_path: Option<&str>,
+    rng: &mut impl Rng,
+) -> Graph<Node, i32> {
     let content = &load_yaml(path)[0];
     let mut dag = Graph::<Node, i32>::new();
 
@@ -44,11 +76,40 @@ fn create_dag_from_yaml(path: &str) -> Graph<Node, i32> {
         .expect("`nodes` field does not exist.");
     for node in nodes {
         let mut params = BTreeMap::new();
+        let mut affinity = None;
         let id = node["id"].as_i64().expect("`id` field does not exist.") as i32;
-        params.insert(
-            "execution_time".to_owned(),
-            choice_execution_time_us(node["execution_time_file"].as_str().unwrap()),
-        );
+        // The execution time is sampled from a file, or given as a constant, e.g., by the DAG generator.
+        match (
+            node["execution_time_file"].as_str(),
+            node["execution_time"].as_i64(),
+        ) {
+            (Some(_), Some(_)) => panic!(
+                "Both `execution_time_file` and `execution_time` are given. DAG: {}, node id: {}",
+                path, id
+            ),
+            (Some(execution_time_file), None) => {
+                let execution_time_path =
+                    resolve_execution_time_file(path, et_dir_path, execution_time_file);
+                if !execution_time_path.is_file() {
+                    panic!(
+                        "Execution time file not found: {}. DAG: {}, node id: {}",
+                        execution_time_path.display(),
+                        path,
+                        id
+                    );
+                }
+                params.insert(
+                    "execution_time".to_owned(),
+                    choice_execution_time_us(&execution_time_path, rng),
+                );
+            }
+            // Loaded below with the other integer parameters.
+            (None, Some(_)) => {}
+            (None, None) => panic!(
+                "Neither `execution_time_file` nor `execution_time` is given. DAG: {}, node id: {}",
+                path, id
+            ),
+        }
 
         // Load node parameters
         for (key, value) in node.as_hash().unwrap() {
@@ -58,9 +119,76 @@ fn create_dag_from_yaml(path: &str) -> Graph<Node, i32> {
             }
 
             match value {
+                // A single integer would be ambiguous between a core id and a bit mask.
+                _ if key_str == "affinity" && value.as_vec().is_none() => panic!(
+                    "`affinity` must be a list of core ids. DAG: {}, node id: {}",
+                    path, id
+                ),
                 Yaml::Integer(_) => {
                     params.insert(key_str.to_owned(), (value.as_i64().unwrap()) as i32);
                 }
+                // The cores a node may run on, given as a list of core ids.
+                Yaml::Array(core_ids) if key_str == "affinity" => {
+                    affinity = Some(
+                        core_ids
+                            .iter()
+                            .map(|core_id| {
+                                core_id
+                                    .as_i64()
+                                    .and_then(|core_id| usize::try_from(core_id).ok())
+                                    .unwrap_or_else(|| {
+                                        panic!(
+                                            "Invalid core id in `affinity`. DAG: {}, node id: {}",
+                                            path, id
+                                        )
+                                    })
+                            })
+                            .collect(),
+                    );
+                }
+                Yaml::String(callback_type) if key_str == "callback_type" => {
+                    let callback_type =
+                        CallbackType::from_name(callback_type).unwrap_or_else(|| {
+                            panic!(
+                                "Unknown callback_type: {}. DAG: {}, node id: {}",
+                                callback_type, path, id
+                            )
+                        });
+                    params.insert(key_str.to_owned(), callback_type as i32);
+                }
+                Yaml::Boolean(conditional) if key_str == "conditional" => {
+                    params.insert(key_str.to_owned(), *conditional as i32);
+                }
+                // The executed times at which the node may be preempted. They must be positive and increasing.
+                Yaml::Array(points) if key_str == "preemption_points" => {
+                    let mut prev_point = 0;
+                    for (i, point) in points.iter().enumerate() {
+                        let point = point
+                            .as_i64()
+                            .map(|point| point as i32)
+                            .filter(|&point| point > prev_point)
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "Invalid executed time in `preemption_points`. DAG: {}, node id: {}",
+                                    path, id
+                                )
+                            });
+                        params.insert(format!("preemption_point.{}", i), point);
+                        prev_point = point;
+                    }
+                }
+                // The successor ids taken by consecutive jobs of a conditional node.
+                Yaml::Array(suc_ids) if key_str == "branch_pattern" => {
+                    for (i, suc_id) in suc_ids.iter().enumerate() {
+                        let suc_id = suc_id.as_i64().unwrap_or_else(|| {
+                            panic!(
+                                "Invalid node id in `branch_pattern`. DAG: {}, node id: {}",
+                                path, id
+                            )
+                        });
+                        params.insert(format!("branch_pattern.{}", i), suc_id as i32);
+                    }
+                }
                 _ => {
                     println!("Non-integer type parameter found: {:?}", value);
                     panic!(
@@ -71,7 +199,11 @@ fn create_dag_from_yaml(path: &str) -> Graph<Node, i32> {
             }
         }
 
-        dag.add_node(Node { id, params });
+        dag.add_node(Node {
+            id,
+            params,
+            affinity,
+        });
     }
 
     // Load edges
@@ -86,12 +218,104 @@ fn create_dag_from_yaml(path: &str) -> Graph<Node, i32> {
             .as_i64()
             .expect("`target` field does not exist()") as usize;
 
-        dag.add_edge(NodeIndex::new(source), NodeIndex::new(target), 0);
+        // The edge weight is the communication delay, given as a constant or sampled like execution times.
+        let delay = match (link["delay"].as_i64(), link["delay_file"].as_str()) {
+            (Some(_), Some(_)) => panic!(
+                "Both `delay` and `delay_file` are given. DAG: {}, link: {} -> {}",
+                path, source, target
+            ),
+            (Some(delay), None) => delay as i32,
+            (None, Some(delay_file)) => {
+                let delay_path = resolve_execution_time_file(path, et_dir_path, delay_file);
+                if !delay_path.is_file() {
+                    panic!(
+                        "Delay file not found: {}. DAG: {}, link: {} -> {}",
+                        delay_path.display(),
+                        path,
+                        source,
+                        target
+                    );
+                }
+                choice_execution_time_us(&delay_path, rng)
+            }
+            (None, None) => 0,
+        };
+
+        dag.add_edge(NodeIndex::new(source), NodeIndex::new(target), delay);
+
+        // The probability that a conditional node takes this edge.
+        if let Some(probability) = link["probability"].as_f64() {
+            let target_id = dag[NodeIndex::new(target)].id;
+            dag.add_param(
+                NodeIndex::new(source),
+                &get_branch_probability_key(target_id),
+                (probability * PROBABILITY_SCALE as f64).round() as i32,
+            );
+        }
     }
+    validate_branches(&dag, path);
 
     dag
 }
 
+fn validate_branches(dag: &Graph<Node, i32>, path: &str) {
+    for node_i in dag.node_indices() {
+        let node = &dag[node_i];
+        let suc_ids = dag
+            .get_suc(node_i)
+            .into_iter()
+            .map(|suc_i| dag[suc_i].id)
+            .collect::<Vec<_>>();
+        let num_probabilities = suc_ids
+            .iter()
+            .filter(|&&suc_id| {
+                node.params
+                    .contains_key(&get_branch_probability_key(suc_id))
+            })
+            .count();
+        if !dag.is_conditional(node_i) {
+            if num_probabilities > 0 || !get_branch_pattern(node).is_empty() {
+                panic!(
+                    "Branches are given for the unconditional node {}. DAG: {}",
+                    node.id, path
+                );
+            }
+            continue;
+        }
+
+        if suc_ids.is_empty() {
+            panic!(
+                "The conditional node {} has no successors. DAG: {}",
+                node.id, path
+            );
+        }
+        if let Some(suc_id) = get_branch_pattern(node)
+            .into_iter()
+            .find(|suc_id| !suc_ids.contains(suc_id))
+        {
+            panic!(
+                "Node {} in the branch pattern of node {} is not its successor. DAG: {}",
+                suc_id, node.id, path
+            );
+        }
+        if num_probabilities > 0 {
+            let total_probability = suc_ids
+                .iter()
+                .map(|&suc_id| node.get_value_or(&get_branch_probability_key(suc_id), 0))
+                .sum::<i32>();
+            if num_probabilities != suc_ids.len()
+                || (total_probability - PROBABILITY_SCALE).abs() > suc_ids.len() as i32
+            {
+                panic!(
+                    "The probabilities of the links from the conditional node {} must be given for all links and \
+                     sum to 1. DAG: {}",
+                    node.id, path
+                );
+            }
+        }
+    }
+}
+
 fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
     if !std::fs::metadata(dir_path).unwrap().is_dir() {
         panic!("Not a directory");
@@ -113,16 +337,434 @@ fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
     yaml_paths
 }
 
-pub fn create_dag_set_from_dir(dir_path: &str) -> Vec<Graph<Node, i32>> {
+/// Execution times are sampled with an RNG seeded by `seed`, so the same seed always yields the same DAGSet.
+/// See `resolve_execution_time_file` for how `et_dir_path` is used.
+pub fn create_dag_set_from_dir(
+    dir_path: &str,
+    et_dir_path: Option<&str>,
+    seed: u64,
+) -> Vec<Graph<Node, i32>> {
     let mut yaml_paths = get_yaml_paths_from_dir(dir_path);
     yaml_paths.sort();
 
+    let mut rng = StdRng::seed_from_u64(seed);
     let mut dag_set: Vec<Graph<Node, i32>> = Vec::new();
     for (dag_id, path) in yaml_paths.iter().enumerate() {
-        let mut dag = create_dag_from_yaml(path);
+        let mut dag = create_dag_from_yaml(path, et_dir_path, &mut rng);
         dag.set_param_to_all_nodes("dag_id", dag_id as i32);
         dag_set.push(dag);
     }
 
     dag_set
 }
+
+/// Write a DAG in the schema of the DAG YAML files with constant execution times, so that it is loaded back as is.
+/// All integer parameters are written as they are stored, except `dag_id`, which the loader sets from the file order.
+pub fn dump_dag_to_yaml(dag: &Graph<Node, i32>, path: &str) {
+    let nodes = dag
+        .node_weights()
+        .map(|node| {
+            let mut mapping = Mapping::new();
+            mapping.insert("id".into(), node.id.into());
+            for (key, &value) in node.params.iter().filter(|(key, _)| *key != "dag_id") {
+                mapping.insert(key.as_str().into(), value.into());
+            }
+            if let Some(affinity) = &node.affinity {
+                mapping.insert(
+                    "affinity".into(),
+                    Value::Sequence(affinity.iter().map(|&core_id| core_id.into()).collect()),
+                );
+            }
+            Value::Mapping(mapping)
+        })
+        .collect::<Vec<_>>();
+    let links = dag
+        .edge_references()
+        .map(|edge| {
+            let mut mapping = Mapping::new();
+            mapping.insert("source".into(), edge.source().index().into());
+            mapping.insert("target".into(), edge.target().index().into());
+            if *edge.weight() != 0 {
+                mapping.insert("delay".into(), (*edge.weight()).into());
+            }
+            Value::Mapping(mapping)
+        })
+        .collect::<Vec<_>>();
+
+    let mut content = Mapping::new();
+    content.insert("nodes".into(), Value::Sequence(nodes));
+    content.insert("links".into(), Value::Sequence(links));
+    let yaml = serde_yaml::to_string(&content).expect("Failed to serialize.");
+    if let Some(dir_path) = Path::new(path).parent() {
+        fs::create_dir_all(dir_path).expect("Failed to create directory.");
+    }
+    fs::write(path, yaml).expect("Failed to write to file.");
+}
+
+#[cfg(test)]
+mod tests_dag_creator {
+    use super::*;
+    use crate::task::dag_generator::{generate_dag_set, DAGGeneratorConfig, GenerationMethod};
+    use std::{collections::BTreeSet, io::Write};
+
+    #[test]
+    fn test_choice_execution_time_us_reproducible() {
+        let et_file_path = std::env::temp_dir().join("test_choice_execution_time_us.txt");
+        let mut et_file = File::create(&et_file_path).unwrap();
+        for et_ns in (1..=100).map(|i| i * 1000) {
+            writeln!(et_file, "{}", et_ns).unwrap();
+        }
+
+        let mut rng0 = StdRng::seed_from_u64(0);
+        let mut rng1 = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            assert_eq!(
+                choice_execution_time_us(&et_file_path, &mut rng0),
+                choice_execution_time_us(&et_file_path, &mut rng1)
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_execution_time_file() {
+        assert_eq!(
+            resolve_execution_time_file("dags/a.yaml", None, "/et/a.txt"),
+            PathBuf::from("/et/a.txt")
+        );
+        assert_eq!(
+            resolve_execution_time_file("dags/a.yaml", Some("et"), "a.txt"),
+            PathBuf::from("et/a.txt")
+        );
+        assert_eq!(
+            resolve_execution_time_file("dags/a.yaml", None, "../et/a.txt"),
+            PathBuf::from("dags/../et/a.txt")
+        );
+        assert_eq!(
+            resolve_execution_time_file("dags/a.yaml", Some("other"), "../et/a.txt"),
+            PathBuf::from("other/a.txt")
+        );
+    }
+
+    #[test]
+    fn test_create_dag_set_from_dir_with_renamed_et_dir() {
+        // The Autoware DAGs refer to `../autoware_execution_times`, but `-e` may point to a directory of another name.
+        let dir_path =
+            std::env::temp_dir().join("test_create_dag_set_from_dir_with_renamed_et_dir");
+        let _ = fs::remove_dir_all(&dir_path);
+        fs::create_dir_all(&dir_path).unwrap();
+        let et_dir_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../autoware_execution_times");
+        for entry in fs::read_dir(et_dir_path).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_file() {
+                fs::copy(&path, dir_path.join(path.file_name().unwrap())).unwrap();
+            }
+        }
+
+        let dag_dir_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../autoware_dags");
+        let get_nodes = |et_dir_path: Option<&str>| {
+            create_dag_set_from_dir(dag_dir_path, et_dir_path, 0)
+                .iter()
+                .map(|dag| dag.node_weights().cloned().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(get_nodes(dir_path.to_str()), get_nodes(None));
+    }
+
+    #[test]
+    fn test_create_dag_set_from_dir_reproducible() {
+        let dag_dir_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../autoware_dags");
+        let et_dir_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../autoware_execution_times");
+        assert_eq!(
+            create_dag_set_from_dir(dag_dir_path, Some(et_dir_path), 0)
+                .iter()
+                .map(|dag| dag.node_weights().cloned().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            create_dag_set_from_dir(dag_dir_path, Some(et_dir_path), 0)
+                .iter()
+                .map(|dag| dag.node_weights().cloned().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_create_dag_set_from_dir_without_et_dir() {
+        // The Autoware DAGs refer to their execution time files relative to their own directory.
+        let dag_dir_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../autoware_dags");
+        let dag_set = create_dag_set_from_dir(dag_dir_path, None, 0);
+        assert_eq!(dag_set.len(), fs::read_dir(dag_dir_path).unwrap().count());
+        for dag in dag_set.iter() {
+            assert!(dag
+                .node_weights()
+                .all(|node| node.get_value("execution_time") > 0));
+        }
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_delay() {
+        let dir_path = std::env::temp_dir().join("test_create_dag_from_yaml_delay");
+        fs::create_dir_all(&dir_path).unwrap();
+        let mut et_file = File::create(dir_path.join("et.txt")).unwrap();
+        writeln!(et_file, "2000").unwrap();
+        let yaml_path = dir_path.join("dag.yaml");
+        let mut yaml_file = File::create(&yaml_path).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time_file: et.txt
+  period: 10
+- id: 1
+  execution_time_file: et.txt
+- id: 2
+  execution_time_file: et.txt
+  relative_deadline: 10
+links:
+- source: 0
+  target: 1
+  delay: 3
+- source: 0
+  target: 2
+  delay_file: et.txt
+- source: 1
+  target: 2
+"
+        )
+        .unwrap();
+
+        let dag = create_dag_from_yaml(
+            yaml_path.to_str().unwrap(),
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
+        let delays = dag
+            .edge_references()
+            .map(|edge| *edge.weight())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, vec![3, 2, 0]);
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_affinity() {
+        let dir_path = std::env::temp_dir().join("test_create_dag_from_yaml_affinity");
+        fs::create_dir_all(&dir_path).unwrap();
+        let mut et_file = File::create(dir_path.join("et.txt")).unwrap();
+        writeln!(et_file, "2000").unwrap();
+        let yaml_path = dir_path.join("dag.yaml");
+        let mut yaml_file = File::create(&yaml_path).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time_file: et.txt
+  period: 10
+  affinity: [0, 2]
+  callback_group: 3
+- id: 1
+  execution_time_file: et.txt
+  relative_deadline: 10
+  affinity: [2]
+  callback_type: service
+links:
+- source: 0
+  target: 1
+"
+        )
+        .unwrap();
+
+        let dag = create_dag_from_yaml(
+            yaml_path.to_str().unwrap(),
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert_eq!(
+            dag[NodeIndex::new(0)].affinity,
+            Some(BTreeSet::from([0, 2]))
+        );
+        assert_eq!(dag[NodeIndex::new(0)].get_value("callback_group"), 3);
+        assert_eq!(dag[NodeIndex::new(1)].affinity, Some(BTreeSet::from([2])));
+        assert_eq!(
+            dag[NodeIndex::new(1)].get_value("callback_type"),
+            CallbackType::Service as i32
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`affinity` must be a list of core ids")]
+    fn test_create_dag_from_yaml_integer_affinity() {
+        let dir_path = std::env::temp_dir().join("test_create_dag_from_yaml_integer_affinity");
+        fs::create_dir_all(&dir_path).unwrap();
+        let yaml_path = dir_path.join("dag.yaml");
+        let mut yaml_file = File::create(&yaml_path).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time: 2
+  period: 10
+  relative_deadline: 10
+  affinity: 2
+links: []
+"
+        )
+        .unwrap();
+
+        create_dag_from_yaml(
+            yaml_path.to_str().unwrap(),
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
+    }
+
+    fn create_dag_with_preemption_points(
+        test_name: &str,
+        preemption_points: &str,
+    ) -> Graph<Node, i32> {
+        let dir_path = std::env::temp_dir().join(test_name);
+        fs::create_dir_all(&dir_path).unwrap();
+        let yaml_path = dir_path.join("dag.yaml");
+        let mut yaml_file = File::create(&yaml_path).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time: 6
+  period: 10
+  relative_deadline: 10
+  preemption_points: {}
+links: []
+",
+            preemption_points
+        )
+        .unwrap();
+
+        create_dag_from_yaml(
+            yaml_path.to_str().unwrap(),
+            None,
+            &mut StdRng::seed_from_u64(0),
+        )
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_preemption_points() {
+        let dag = create_dag_with_preemption_points(
+            "test_create_dag_from_yaml_preemption_points",
+            "[2, 5]",
+        );
+        assert_eq!(dag[NodeIndex::new(0)].get_preemption_points(), vec![2, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid executed time in `preemption_points`")]
+    fn test_create_dag_from_yaml_unordered_preemption_points() {
+        create_dag_with_preemption_points(
+            "test_create_dag_from_yaml_unordered_preemption_points",
+            "[5, 2]",
+        );
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_conditional() {
+        let dir_path = std::env::temp_dir().join("test_create_dag_from_yaml_conditional");
+        fs::create_dir_all(&dir_path).unwrap();
+        let mut et_file = File::create(dir_path.join("et.txt")).unwrap();
+        writeln!(et_file, "2000").unwrap();
+        let yaml_path = dir_path.join("dag.yaml");
+        let mut yaml_file = File::create(&yaml_path).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time_file: et.txt
+  period: 10
+  conditional: true
+- id: 1
+  execution_time_file: et.txt
+- id: 2
+  execution_time_file: et.txt
+  conditional: true
+  branch_pattern: [3, 3, 4]
+- id: 3
+  execution_time_file: et.txt
+- id: 4
+  execution_time_file: et.txt
+links:
+- source: 0
+  target: 1
+  probability: 0.25
+- source: 0
+  target: 2
+  probability: 0.75
+- source: 2
+  target: 3
+- source: 2
+  target: 4
+"
+        )
+        .unwrap();
+
+        let dag = create_dag_from_yaml(
+            yaml_path.to_str().unwrap(),
+            None,
+            &mut StdRng::seed_from_u64(0),
+        );
+        let n0 = &dag[NodeIndex::new(0)];
+        assert!(dag.is_conditional(NodeIndex::new(0)));
+        assert_eq!(n0.get_value("branch_probability.1"), 250_000);
+        assert_eq!(n0.get_value("branch_probability.2"), 750_000);
+        assert_eq!(get_branch_pattern(&dag[NodeIndex::new(2)]), vec![3, 3, 4]);
+        assert!(!dag.is_conditional(NodeIndex::new(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "node id: 0")]
+    fn test_create_dag_set_from_dir_missing_execution_time_file() {
+        let dir_path =
+            std::env::temp_dir().join("test_create_dag_set_from_dir_missing_execution_time_file");
+        let _ = fs::remove_dir_all(&dir_path);
+        fs::create_dir_all(&dir_path).unwrap();
+        let mut yaml_file = File::create(dir_path.join("dag.yaml")).unwrap();
+        write!(
+            yaml_file,
+            "nodes:
+- id: 0
+  execution_time_file: missing.txt
+  period: 10
+links: []
+"
+        )
+        .unwrap();
+
+        create_dag_set_from_dir(dir_path.to_str().unwrap(), None, 0);
+    }
+
+    #[test]
+    fn test_dump_dag_to_yaml_round_trip() {
+        let dir_path = std::env::temp_dir().join("test_dump_dag_to_yaml_round_trip");
+        let _ = fs::remove_dir_all(&dir_path);
+        let config = DAGGeneratorConfig {
+            method: GenerationMethod::ErdosRenyi,
+            ..Default::default()
+        };
+        let mut dag_set = generate_dag_set(&config, 2, 0);
+        dag_set[0].update_edge(NodeIndex::new(0), NodeIndex::new(1), 5);
+        dag_set[1][NodeIndex::new(0)].affinity = Some(BTreeSet::from([1, 3]));
+        for (dag_id, dag) in dag_set.iter().enumerate() {
+            let path = dir_path.join(format!("dag_{}.yaml", dag_id));
+            dump_dag_to_yaml(dag, path.to_str().unwrap());
+        }
+
+        let loaded_dag_set = create_dag_set_from_dir(dir_path.to_str().unwrap(), None, 0);
+        for (dag, loaded_dag) in dag_set.iter().zip(&loaded_dag_set) {
+            assert_eq!(
+                dag.node_weights().collect::<Vec<_>>(),
+                loaded_dag.node_weights().collect::<Vec<_>>()
+            );
+            let to_edges = |dag: &Graph<Node, i32>| {
+                dag.edge_references()
+                    .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(to_edges(dag), to_edges(loaded_dag));
+        }
+    }
+}
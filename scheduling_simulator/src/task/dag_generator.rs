@@ -0,0 +1,317 @@
+//! Generate random DAGs for synthetic workloads.
+//! Every generated DAG has a single source, node ids equal to node indices, no communication delays, and execution
+//! times drawn uniformly from [`min_execution_time`, `max_execution_time`]. The `period` is set on the source and the
+//! `relative_deadline` (equal to the period) on each sink, as in the DAG YAML files, so that the generated DAGs can
+//! be written with `dump_dag_to_yaml` and loaded again with `create_dag_set_from_dir`.
+
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use petgraph::{graph::Graph, prelude::*};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::dag::{Node, DAG};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum GenerationMethod {
+    /// Each pair of nodes i < j is linked with `edge_probability` (Erdős–Rényi). Nodes without predecessors are
+    /// linked from node 0, the source. `depth` is not used.
+    ErdosRenyi,
+    /// The source is the first of `depth` layers, and the other nodes are spread over the other layers. Each node is
+    /// linked from each node of the earlier layers with `edge_probability`, and from at least one node of the previous
+    /// layer, so the DAG has exactly `depth` layers.
+    Layered,
+    /// A random node is repeatedly replaced with a fork-join of 2 to `max_branches` branches, nested at most `depth`
+    /// times. Nodes at the maximum nesting depth are extended in series instead.
+    NestedForkJoin,
+}
+
+#[derive(Clone, Debug)]
+pub struct DAGGeneratorConfig {
+    pub method: GenerationMethod,
+    pub num_nodes: usize,
+    pub edge_probability: f64,
+    pub depth: usize,
+    pub max_branches: usize,
+    pub min_execution_time: i32,
+    pub max_execution_time: i32,
+    pub period: i32,
+}
+
+impl Default for DAGGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            method: GenerationMethod::Layered,
+            num_nodes: 10,
+            edge_probability: 0.3,
+            depth: 4,
+            max_branches: 3,
+            min_execution_time: 1,
+            max_execution_time: 100,
+            period: 1000,
+        }
+    }
+}
+
+impl DAGGeneratorConfig {
+    fn validate(&self) {
+        if self.num_nodes == 0 {
+            panic!("A DAG needs at least one node.");
+        }
+        if !(0.0..=1.0).contains(&self.edge_probability) {
+            panic!(
+                "The edge probability {} is not in [0, 1].",
+                self.edge_probability
+            );
+        }
+        if self.method == GenerationMethod::Layered {
+            if !(1..=self.num_nodes).contains(&self.depth) {
+                panic!(
+                    "The depth {} must be between 1 and the number of nodes {}.",
+                    self.depth, self.num_nodes
+                );
+            }
+            // The source is alone in the first layer, so the other nodes need a second layer.
+            if self.depth == 1 && self.num_nodes > 1 {
+                panic!(
+                    "The depth must be at least 2 to place the {} nodes other than the source.",
+                    self.num_nodes - 1
+                );
+            }
+        }
+        if self.method == GenerationMethod::NestedForkJoin && self.max_branches < 2 {
+            panic!("A fork-join needs at least 2 branches.");
+        }
+        if self.min_execution_time <= 0 || self.min_execution_time > self.max_execution_time {
+            panic!(
+                "Invalid execution time range: [{}, {}].",
+                self.min_execution_time, self.max_execution_time
+            );
+        }
+    }
+}
+
+fn add_node(
+    dag: &mut Graph<Node, i32>,
+    config: &DAGGeneratorConfig,
+    rng: &mut impl Rng,
+) -> NodeIndex {
+    let execution_time = rng.gen_range(config.min_execution_time..=config.max_execution_time);
+    dag.add_node(Node::new(
+        dag.node_count() as i32,
+        BTreeMap::from([("execution_time".to_owned(), execution_time)]),
+    ))
+}
+
+fn generate_erdos_renyi(
+    dag: &mut Graph<Node, i32>,
+    config: &DAGGeneratorConfig,
+    rng: &mut impl Rng,
+) {
+    let nodes = (0..config.num_nodes)
+        .map(|_| add_node(dag, config, rng))
+        .collect::<Vec<_>>();
+    for (j, &node_i) in nodes.iter().enumerate().skip(1) {
+        for &pre_i in &nodes[..j] {
+            if rng.gen_bool(config.edge_probability) {
+                dag.add_edge(pre_i, node_i, 0);
+            }
+        }
+        if dag.get_pre(node_i).is_empty() {
+            dag.add_edge(nodes[0], node_i, 0);
+        }
+    }
+}
+
+fn generate_layered(dag: &mut Graph<Node, i32>, config: &DAGGeneratorConfig, rng: &mut impl Rng) {
+    // The source is the only node of the first layer, and each other layer has at least one node.
+    let mut layer_sizes = vec![1; config.depth];
+    for _ in config.depth..config.num_nodes {
+        layer_sizes[rng.gen_range(1..config.depth)] += 1;
+    }
+
+    let mut layers: Vec<Vec<NodeIndex>> = Vec::new();
+    for layer_size in layer_sizes {
+        let layer = (0..layer_size)
+            .map(|_| add_node(dag, config, rng))
+            .collect::<Vec<_>>();
+        if let Some(prev_layer) = layers.last() {
+            for &node_i in &layer {
+                for &pre_i in layers.iter().flatten() {
+                    if rng.gen_bool(config.edge_probability) {
+                        dag.add_edge(pre_i, node_i, 0);
+                    }
+                }
+                if !prev_layer
+                    .iter()
+                    .any(|&pre_i| dag.find_edge(pre_i, node_i).is_some())
+                {
+                    dag.add_edge(*prev_layer.choose(rng).unwrap(), node_i, 0);
+                }
+            }
+        }
+        layers.push(layer);
+    }
+}
+
+fn generate_nested_fork_join(
+    dag: &mut Graph<Node, i32>,
+    config: &DAGGeneratorConfig,
+    rng: &mut impl Rng,
+) {
+    // (node, nesting depth) of the nodes that have not been expanded yet
+    let mut leaves = vec![(add_node(dag, config, rng), 0)];
+    while dag.node_count() < config.num_nodes {
+        let (node_i, nesting_depth) = leaves.swap_remove(rng.gen_range(0..leaves.len()));
+        let num_remaining_nodes = config.num_nodes - dag.node_count();
+        let num_branches = if nesting_depth < config.depth {
+            rng.gen_range(2..=config.max_branches)
+                .min(num_remaining_nodes - 1)
+        } else {
+            0
+        };
+
+        // The join node takes over the successors of the expanded node.
+        let join_i = add_node(dag, config, rng);
+        for suc_i in dag.get_suc(node_i) {
+            let edge_i = dag.find_edge(node_i, suc_i).unwrap();
+            dag.remove_edge(edge_i);
+            dag.add_edge(join_i, suc_i, 0);
+        }
+        if num_branches == 0 {
+            dag.add_edge(node_i, join_i, 0);
+        }
+        for _ in 0..num_branches {
+            let branch_i = add_node(dag, config, rng);
+            dag.add_edge(node_i, branch_i, 0);
+            dag.add_edge(branch_i, join_i, 0);
+            leaves.push((branch_i, nesting_depth + 1));
+        }
+        leaves.push((join_i, nesting_depth));
+    }
+}
+
+pub fn generate_dag(config: &DAGGeneratorConfig, rng: &mut impl Rng) -> Graph<Node, i32> {
+    config.validate();
+    let mut dag = Graph::<Node, i32>::new();
+    match config.method {
+        GenerationMethod::ErdosRenyi => generate_erdos_renyi(&mut dag, config, rng),
+        GenerationMethod::Layered => generate_layered(&mut dag, config, rng),
+        GenerationMethod::NestedForkJoin => generate_nested_fork_join(&mut dag, config, rng),
+    }
+
+    let source_i = dag.get_source();
+    dag.add_param(source_i, "period", config.period);
+    for sink_i in dag.get_sink() {
+        dag.add_param(sink_i, "relative_deadline", config.period);
+    }
+
+    dag
+}
+
+/// The same seed always yields the same DAG set. `dag_id` is set in the order of the DAG set as the loader does.
+pub fn generate_dag_set(
+    config: &DAGGeneratorConfig,
+    num_dags: usize,
+    seed: u64,
+) -> Vec<Graph<Node, i32>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..num_dags)
+        .map(|dag_id| {
+            let mut dag = generate_dag(config, &mut rng);
+            dag.set_param_to_all_nodes("dag_id", dag_id as i32);
+            dag
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_dag_generator {
+    use super::*;
+
+    fn generate(method: GenerationMethod, seed: u64) -> Graph<Node, i32> {
+        let config = DAGGeneratorConfig {
+            method,
+            num_nodes: 20,
+            ..Default::default()
+        };
+        generate_dag(&config, &mut StdRng::seed_from_u64(seed))
+    }
+
+    #[test]
+    fn test_generate_dag_common_properties() {
+        for method in GenerationMethod::value_variants() {
+            for seed in 0..20 {
+                let dag = generate(*method, seed);
+                assert_eq!(dag.node_count(), 20);
+                assert!(!petgraph::algo::is_cyclic_directed(&dag));
+                let source_i = dag.get_source();
+                assert_eq!(dag[source_i].get_value("period"), 1000);
+                for sink_i in dag.get_sink() {
+                    assert_eq!(dag[sink_i].get_value("relative_deadline"), 1000);
+                }
+                for (node_i, node) in dag.node_indices().zip(dag.node_weights()) {
+                    assert_eq!(node.id as usize, node_i.index());
+                    assert!((1..=100).contains(&node.get_value("execution_time")));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_layered_depth() {
+        for seed in 0..20 {
+            assert_eq!(generate(GenerationMethod::Layered, seed).get_depth(), 4);
+        }
+    }
+
+    #[test]
+    fn test_generate_layered_single_node() {
+        let config = DAGGeneratorConfig {
+            num_nodes: 1,
+            depth: 1,
+            ..Default::default()
+        };
+        let dag = generate_dag(&config, &mut StdRng::seed_from_u64(0));
+        assert_eq!(dag.node_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "The depth must be at least 2")]
+    fn test_generate_layered_depth_one_with_multiple_nodes() {
+        let config = DAGGeneratorConfig {
+            num_nodes: 3,
+            depth: 1,
+            ..Default::default()
+        };
+        generate_dag(&config, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn test_generate_nested_fork_join_single_sink() {
+        for seed in 0..20 {
+            let dag = generate(GenerationMethod::NestedForkJoin, seed);
+            assert_eq!(dag.get_sink().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_generate_dag_set_reproducible() {
+        let config = DAGGeneratorConfig::default();
+        let to_params = |dag_set: Vec<Graph<Node, i32>>| {
+            dag_set
+                .iter()
+                .map(|dag| {
+                    (
+                        dag.node_weights().cloned().collect::<Vec<_>>(),
+                        dag.edge_count(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            to_params(generate_dag_set(&config, 3, 0)),
+            to_params(generate_dag_set(&config, 3, 0))
+        );
+    }
+}
@@ -1,7 +1,69 @@
+use std::fmt;
+
 use num_integer::lcm;
 use petgraph::Graph;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    arrival::mix,
+    dag::{Node, DAG},
+};
+
+const MAX_UUNIFAST_ATTEMPTS: usize = 100_000;
 
-use super::dag::{Node, DAG};
+/// Parameters in time units that are relative to the period, and so are scaled with it.
+const PERIOD_RELATIVE_PARAMS: [&str; 4] = [
+    "relative_deadline",
+    "offset",
+    "release_jitter",
+    "inter_arrival_slack",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeriodScalingError {
+    /// Every critical path would take its whole deadline at `max_total_utilization`.
+    InfeasibleUtilization {
+        total_utilization: f64,
+        max_total_utilization: f64,
+    },
+    /// Every period would be the longest one at which no scaled parameter exceeds `max_period` at
+    /// `min_total_utilization`.
+    TooLowUtilization {
+        total_utilization: f64,
+        min_total_utilization: f64,
+    },
+    UUniFastDiscardFailed {
+        total_utilization: f64,
+    },
+}
+
+impl fmt::Display for PeriodScalingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeriodScalingError::InfeasibleUtilization {
+                total_utilization,
+                max_total_utilization,
+            } => write!(
+                f,
+                "The total utilization {} exceeds {}, at which every critical path takes its whole deadline.",
+                total_utilization, max_total_utilization
+            ),
+            PeriodScalingError::TooLowUtilization {
+                total_utilization,
+                min_total_utilization,
+            } => write!(
+                f,
+                "The total utilization {} is below {}, at which every period is the longest allowed one.",
+                total_utilization, min_total_utilization
+            ),
+            PeriodScalingError::UUniFastDiscardFailed { total_utilization } => write!(
+                f,
+                "UUniFast-Discard failed to split the total utilization {} in {} attempts.",
+                total_utilization, MAX_UUNIFAST_ATTEMPTS
+            ),
+        }
+    }
+}
 
 pub fn get_hyper_period(dag_set: &[Graph<Node, i32>]) -> i32 {
     let mut hyper_period = 1;
@@ -12,16 +74,147 @@ pub fn get_hyper_period(dag_set: &[Graph<Node, i32>]) -> i32 {
     hyper_period
 }
 
+/// Split `total_utilization` into `max_utilizations.len()` utilizations uniformly at random (UUniFast, Bini and
+/// Buttazzo), and draw again while any utilization is out of its range (UUniFast-Discard, Davis and Burns).
+/// Returns None if no valid split is drawn in `MAX_UUNIFAST_ATTEMPTS` attempts.
+pub fn uunifast_discard(
+    total_utilization: f64,
+    min_utilizations: &[f64],
+    max_utilizations: &[f64],
+    rng: &mut impl Rng,
+) -> Option<Vec<f64>> {
+    let num_tasks = max_utilizations.len();
+    (0..MAX_UUNIFAST_ATTEMPTS).find_map(|_| {
+        let mut remaining_utilization = total_utilization;
+        let mut utilizations = Vec::with_capacity(num_tasks);
+        for i in 1..num_tasks {
+            let next_remaining_utilization =
+                remaining_utilization * rng.gen::<f64>().powf(1.0 / (num_tasks - i) as f64);
+            utilizations.push(remaining_utilization - next_remaining_utilization);
+            remaining_utilization = next_remaining_utilization;
+        }
+        utilizations.push(remaining_utilization);
+
+        utilizations
+            .iter()
+            .zip(min_utilizations.iter().zip(max_utilizations))
+            .all(|(utilization, (min_utilization, max_utilization))| {
+                (min_utilization..=max_utilization).contains(&utilization)
+            })
+            .then_some(utilizations)
+    })
+}
+
+/// Scale the period of each DAG so that the DAG set has `total_utilization`, distributed over the DAGs with
+/// UUniFast-Discard. The relative deadlines, offsets, release jitters and inter-arrival slacks are scaled with the
+/// periods, and the execution times are kept.
+/// A DAG is given at most the utilization at which its critical path, including the communication delays, still fits in
+/// its earliest relative deadline, and at least the one at which neither its period nor any parameter scaled with it
+/// exceeds `max_period`, e.g., the simulation duration. Draws that would stretch a parameter beyond `max_period` are
+/// thus discarded rather than capped, which would distort its ratio to the period.
+/// Periods are rounded to integers, so the resulting utilization is close to but not exactly the target.
+/// On an error, the DAG set is left unchanged.
+pub fn scale_periods_to_utilization(
+    dag_set: &mut [Graph<Node, i32>],
+    total_utilization: f64,
+    max_period: i32,
+    seed: u64,
+) -> Result<(), PeriodScalingError> {
+    let max_utilizations = dag_set
+        .iter()
+        .map(|dag| {
+            let min_relative_deadline = dag
+                .node_weights()
+                .filter_map(|node| node.params.get("relative_deadline"))
+                .min()
+                .copied()
+                .expect("No relative deadline found.");
+            dag.get_volume() as f64 * min_relative_deadline as f64
+                / (dag.get_dag_param("period") as f64
+                    * dag.get_critical_path_length_with_delays() as f64)
+        })
+        .collect::<Vec<_>>();
+    // The longest period at which no parameter scaled with it exceeds `max_period`.
+    let max_new_periods = dag_set
+        .iter()
+        .map(|dag| {
+            let period = dag.get_dag_param("period");
+            let max_value = dag
+                .node_weights()
+                .flat_map(|node| {
+                    PERIOD_RELATIVE_PARAMS
+                        .iter()
+                        .filter_map(|key| node.params.get(*key).copied())
+                })
+                .fold(period, i32::max);
+            (period as i64 * max_period as i64 / max_value as i64) as i32
+        })
+        .collect::<Vec<_>>();
+    let min_utilizations = dag_set
+        .iter()
+        .zip(&max_new_periods)
+        .map(|(dag, &max_new_period)| dag.get_volume() as f64 / max_new_period as f64)
+        .collect::<Vec<_>>();
+    let max_total_utilization = max_utilizations.iter().sum::<f64>();
+    if total_utilization > max_total_utilization {
+        return Err(PeriodScalingError::InfeasibleUtilization {
+            total_utilization,
+            max_total_utilization,
+        });
+    }
+    let min_total_utilization = min_utilizations.iter().sum::<f64>();
+    if total_utilization < min_total_utilization {
+        return Err(PeriodScalingError::TooLowUtilization {
+            total_utilization,
+            min_total_utilization,
+        });
+    }
+
+    // Drawn from another stream than the offsets, which are drawn from mix(seed).
+    let mut rng = StdRng::seed_from_u64(mix(mix(seed)));
+    let utilizations = uunifast_discard(
+        total_utilization,
+        &min_utilizations,
+        &max_utilizations,
+        &mut rng,
+    )
+    .ok_or(PeriodScalingError::UUniFastDiscardFailed { total_utilization })?;
+
+    for ((dag, utilization), max_new_period) in
+        dag_set.iter_mut().zip(utilizations).zip(max_new_periods)
+    {
+        let period = dag.get_dag_param("period");
+        let new_period =
+            ((dag.get_volume() as f64 / utilization).round() as i32).clamp(1, max_new_period);
+        for node_i in dag.node_indices() {
+            if dag[node_i].params.contains_key("period") {
+                dag.update_param(node_i, "period", new_period);
+            }
+            for key in PERIOD_RELATIVE_PARAMS {
+                if let Some(&value) = dag[node_i].params.get(key) {
+                    let new_value =
+                        (value as f64 * new_period as f64 / period as f64).round() as i32;
+                    dag.update_param(node_i, key, new_value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::test_util::create_fork_join_dag;
+    use crate::task::dag_generator::{generate_dag_set, DAGGeneratorConfig};
     use std::collections::BTreeMap;
 
     fn create_dag_with_period(period: i32) -> Graph<Node, i32> {
         let mut dag = Graph::<Node, i32>::new();
         let mut params = BTreeMap::new();
         params.insert("period".to_string(), period);
-        dag.add_node(Node { id: 0, params });
+        dag.add_node(Node::new(0, params));
 
         dag
     }
@@ -36,4 +229,135 @@ mod tests {
         ];
         assert_eq!(get_hyper_period(&dag_set), 120);
     }
+
+    #[test]
+    fn test_uunifast_discard() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let utilizations =
+                uunifast_discard(1.5, &[0.0; 3], &[1.0, 1.0, 0.5], &mut rng).unwrap();
+            assert_eq!(utilizations.len(), 3);
+            assert!((utilizations.iter().sum::<f64>() - 1.5).abs() < 1e-9);
+            assert!(utilizations[2] <= 0.5);
+        }
+        assert_eq!(
+            uunifast_discard(2.5, &[0.0; 2], &[1.0, 1.0], &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scale_periods_to_utilization() {
+        let config = DAGGeneratorConfig::default();
+        for seed in 0..10 {
+            let mut dag_set = generate_dag_set(&config, 8, seed);
+            scale_periods_to_utilization(&mut dag_set, 2.0, i32::MAX, seed).unwrap();
+            let total_utilization = dag_set.iter().map(|dag| dag.get_utilization()).sum::<f32>();
+            assert!(
+                (total_utilization - 2.0).abs() < 0.01,
+                "{}",
+                total_utilization
+            );
+            for dag in dag_set.iter() {
+                let period = dag.get_dag_param("period");
+                for sink_i in dag.get_sink() {
+                    // The generated DAGs have implicit deadlines.
+                    assert_eq!(dag[sink_i].get_value("relative_deadline"), period);
+                }
+                assert!(dag.get_critical_path_length() <= period);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_periods_to_utilization_scales_arrival_params() {
+        let mut dag_set = generate_dag_set(&DAGGeneratorConfig::default(), 1, 0);
+        let period = dag_set[0].get_dag_param("period");
+        for (key, value) in [
+            ("offset", period / 2),
+            ("release_jitter", period / 4),
+            ("inter_arrival_slack", period),
+        ] {
+            dag_set[0].set_param_to_all_nodes(key, value);
+        }
+        scale_periods_to_utilization(&mut dag_set, 0.5, i32::MAX, 0).unwrap();
+
+        let new_period = dag_set[0].get_dag_param("period");
+        let scale = |value: i32| (value as f64 * new_period as f64 / period as f64).round() as i32;
+        assert_eq!(dag_set[0].get_dag_param("offset"), scale(period / 2));
+        assert_eq!(
+            dag_set[0].get_dag_param("release_jitter"),
+            scale(period / 4)
+        );
+        assert_eq!(dag_set[0].get_dag_param("inter_arrival_slack"), new_period);
+    }
+
+    #[test]
+    fn test_scale_periods_to_near_zero_utilization_shares() {
+        let max_period = 1_000_000;
+        for seed in 0..10 {
+            let mut dag_set = generate_dag_set(&DAGGeneratorConfig::default(), 16, seed);
+            dag_set[0].set_param_to_all_nodes("inter_arrival_slack", 1000);
+            scale_periods_to_utilization(&mut dag_set, 0.2, max_period, seed).unwrap();
+            for dag in dag_set.iter() {
+                assert!((1..=max_period).contains(&dag.get_dag_param("period")));
+            }
+            assert!(dag_set[0].get_dag_param("inter_arrival_slack") <= max_period);
+        }
+
+        let mut dag_set = generate_dag_set(&DAGGeneratorConfig::default(), 2, 0);
+        assert!(matches!(
+            scale_periods_to_utilization(&mut dag_set, 1e-9, max_period, 0),
+            Err(PeriodScalingError::TooLowUtilization { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scale_periods_to_infeasible_utilization() {
+        let mut dag_set = generate_dag_set(&DAGGeneratorConfig::default(), 2, 0);
+        let periods = dag_set
+            .iter()
+            .map(|dag| dag.get_dag_param("period"))
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            scale_periods_to_utilization(&mut dag_set, 100.0, i32::MAX, 0),
+            Err(PeriodScalingError::InfeasibleUtilization { .. })
+        ));
+        for (dag, period) in dag_set.iter().zip(periods) {
+            assert_eq!(dag.get_dag_param("period"), period);
+        }
+    }
+
+    #[test]
+    fn test_scale_periods_with_communication_delays() {
+        // The critical path is 3 without the delays and 11 with them.
+        let mut dag = create_fork_join_dag(0, 10, &[1, 1, 1]);
+        dag.edge_weights_mut().for_each(|delay| *delay = 4);
+        let mut dag_set = vec![dag];
+        assert!(matches!(
+            scale_periods_to_utilization(&mut dag_set, 0.5, i32::MAX, 0),
+            Err(PeriodScalingError::InfeasibleUtilization { .. })
+        ));
+
+        scale_periods_to_utilization(&mut dag_set, 0.25, i32::MAX, 0).unwrap();
+        assert!(
+            dag_set[0].get_critical_path_length_with_delays() <= dag_set[0].get_dag_param("period")
+        );
+    }
+
+    #[test]
+    fn test_scale_periods_keeps_offsets_within_max_period() {
+        // The offset is twice the period, so the period may reach only half of `max_period`.
+        let mut dag = create_fork_join_dag(0, 10, &[1, 1, 1]);
+        dag.set_param_to_all_nodes("offset", 20);
+        let mut dag_set = vec![dag];
+        assert!(matches!(
+            scale_periods_to_utilization(&mut dag_set, 0.04, 100, 0),
+            Err(PeriodScalingError::TooLowUtilization { .. })
+        ));
+
+        scale_periods_to_utilization(&mut dag_set, 0.06, 100, 0).unwrap();
+        assert_eq!(dag_set[0].get_dag_param("period"), 50);
+        assert_eq!(dag_set[0].get_dag_param("offset"), 100);
+    }
 }
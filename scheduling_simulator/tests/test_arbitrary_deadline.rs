@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, IntraTaskPrecedence, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+// A job takes 4 time units but arrives every 2 time units, so up to 3 jobs are in flight.
+fn create_overlapping_dag_set() -> Vec<Graph<Node, i32>> {
+    let mut dag = Graph::<Node, i32>::new();
+    let n0 = dag.add_node(Node::new(
+        0,
+        BTreeMap::from([("execution_time".to_string(), 1), ("period".to_string(), 2)]),
+    ));
+    let n1 = dag.add_node(Node::new(
+        1,
+        BTreeMap::from([
+            ("execution_time".to_string(), 3),
+            ("relative_deadline".to_string(), 6),
+        ]),
+    ));
+    dag.add_edge(n0, n1, 0);
+    dag.set_param_to_all_nodes("dag_id", 0);
+
+    vec![dag]
+}
+
+fn schedule(intra_task_precedence: IntraTaskPrecedence) -> Vec<i32> {
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalEDFScheduler::new(&create_overlapping_dag_set(), &processor);
+    scheduler.get_config_mut().intra_task_precedence = intra_task_precedence;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        10,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    log.dag_set_log[0].response_times_per_sink[&1].clone()
+}
+
+#[test]
+fn test_overlapping_jobs_independent() {
+    assert_eq!(schedule(IntraTaskPrecedence::Independent), vec![4, 4, 4, 4]);
+}
+
+#[test]
+fn test_overlapping_jobs_node_precedence() {
+    // Node 1 of job k + 1 waits for node 1 of job k.
+    assert_eq!(schedule(IntraTaskPrecedence::Node), vec![4, 5, 6]);
+}
+
+#[test]
+fn test_overlapping_jobs_job_precedence() {
+    // Node 0 of job k + 1 waits for the completion of job k.
+    assert_eq!(schedule(IntraTaskPrecedence::Job), vec![4, 6]);
+}
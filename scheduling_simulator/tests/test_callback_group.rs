@@ -0,0 +1,57 @@
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::Node,
+};
+
+mod util;
+use util::create_single_node_dag_set;
+
+// Single-node DAGs released at the same time, with relative deadlines in the order of dag_id.
+fn create_dag_set(callback_groups: &[Option<i32>]) -> Vec<Graph<Node, i32>> {
+    let params_per_dag = callback_groups
+        .iter()
+        .enumerate()
+        .map(|(dag_id, callback_group)| {
+            let mut params = vec![
+                ("execution_time", 3),
+                ("relative_deadline", 10 + dag_id as i32),
+            ];
+            if let Some(callback_group) = callback_group {
+                params.push(("callback_group", *callback_group));
+            }
+            params
+        })
+        .collect::<Vec<_>>();
+    create_single_node_dag_set(&params_per_dag)
+}
+
+fn schedule(callback_groups: &[Option<i32>]) -> Vec<i32> {
+    let processor = HomogeneousProcessor::new(3);
+    let mut scheduler = GlobalEDFScheduler::new(&create_dag_set(callback_groups), &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        20,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    log.dag_set_log
+        .iter()
+        .map(|dag_log| dag_log.response_times_per_sink[&0][0])
+        .collect()
+}
+
+#[test]
+fn test_callback_group_mutual_exclusion() {
+    assert_eq!(schedule(&[None, None, None]), vec![3, 3, 3]);
+    // DAGs 0 and 2 share a callback group, so DAG 2 waits for DAG 0 although a core is idle.
+    assert_eq!(schedule(&[Some(0), None, Some(0)]), vec![3, 3, 6]);
+    assert_eq!(schedule(&[Some(0), Some(0), Some(0)]), vec![3, 6, 9]);
+}
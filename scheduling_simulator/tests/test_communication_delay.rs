@@ -0,0 +1,88 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{CommunicationDelayMode, DAGSetSchedulerBase, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+// Two nodes of 1 time unit connected by an edge with a delay of 2.
+fn create_delayed_dag_set(suc_affinity: Option<BTreeSet<usize>>) -> Vec<Graph<Node, i32>> {
+    let mut dag = Graph::<Node, i32>::new();
+    let n0 = dag.add_node(Node::new(
+        0,
+        BTreeMap::from([
+            ("execution_time".to_string(), 1),
+            ("period".to_string(), 10),
+        ]),
+    ));
+    let mut suc = Node::new(
+        1,
+        BTreeMap::from([
+            ("execution_time".to_string(), 1),
+            ("relative_deadline".to_string(), 10),
+        ]),
+    );
+    suc.affinity = suc_affinity;
+    let n1 = dag.add_node(suc);
+    dag.add_edge(n0, n1, 2);
+    dag.set_param_to_all_nodes("dag_id", 0);
+
+    vec![dag]
+}
+
+fn schedule(
+    communication_delay: CommunicationDelayMode,
+    suc_affinity: Option<BTreeSet<usize>>,
+) -> (Vec<i32>, i32) {
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalEDFScheduler::new(&create_delayed_dag_set(suc_affinity), &processor);
+    scheduler.get_config_mut().communication_delay = communication_delay;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        30,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    (
+        log.dag_set_log[0].response_times_per_sink[&1].clone(),
+        scheduler
+            .get_processor()
+            .get_total_overhead_times()
+            .iter()
+            .sum(),
+    )
+}
+
+#[test]
+fn test_communication_delay_always() {
+    assert_eq!(
+        schedule(CommunicationDelayMode::Always, None),
+        (vec![4, 4, 4], 0)
+    );
+}
+
+#[test]
+fn test_communication_delay_same_core() {
+    // The successor runs on the core of its predecessor, so the delay does not apply.
+    assert_eq!(
+        schedule(CommunicationDelayMode::CrossCore, None),
+        (vec![2, 2, 2], 0)
+    );
+}
+
+#[test]
+fn test_communication_delay_cross_core() {
+    // The successor is pinned to the other core and waits until the data arrives there, which is not overhead.
+    assert_eq!(
+        schedule(CommunicationDelayMode::CrossCore, Some(BTreeSet::from([1]))),
+        (vec![4, 4, 4], 0)
+    );
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+// Node 0 is a conditional node that alternates between its successors with the branch pattern.
+fn create_conditional_dag(execution_times: &[i32], edges: &[(u32, u32)]) -> Graph<Node, i32> {
+    let mut dag = Graph::<Node, i32>::new();
+    for (id, &execution_time) in execution_times.iter().enumerate() {
+        let mut params = BTreeMap::from([
+            ("execution_time".to_string(), execution_time),
+            ("period".to_string(), 10),
+            ("relative_deadline".to_string(), 10),
+        ]);
+        if id == 0 {
+            params.insert("conditional".to_string(), 1);
+            params.insert("branch_pattern.0".to_string(), 1);
+            params.insert("branch_pattern.1".to_string(), 2);
+        }
+        dag.add_node(Node::new(id as i32, params));
+    }
+    for &(source, target) in edges {
+        dag.add_edge(source.into(), target.into(), 0);
+    }
+    dag.set_param_to_all_nodes("dag_id", 0);
+    dag
+}
+
+fn schedule(dag: Graph<Node, i32>) -> GlobalEDFScheduler {
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = GlobalEDFScheduler::new(&[dag], &processor);
+    scheduler.get_config_mut().continue_after_deadline_miss = true;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        40,
+    );
+    scheduler
+}
+
+#[test]
+fn test_conditional_branches_join() {
+    // The join node 3 runs after whichever branch is taken.
+    let dag = create_conditional_dag(&[1, 5, 2, 1], &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    let scheduler = schedule(dag);
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    assert_eq!(
+        log.dag_set_log[0].response_times_per_sink[&3],
+        vec![7, 4, 7, 4]
+    );
+}
+
+#[test]
+fn test_conditional_skipped_sink() {
+    // Sink 1 is only reached by even jobs, and sink 3 by odd jobs through node 2.
+    let dag = create_conditional_dag(&[1, 3, 2, 2], &[(0, 1), (0, 2), (2, 3)]);
+    let scheduler = schedule(dag);
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    let dag_log = &log.dag_set_log[0];
+    assert_eq!(dag_log.response_times_per_sink[&1], vec![4, 4]);
+    assert_eq!(dag_log.response_times_per_sink[&3], vec![5, 5]);
+    assert!(dag_log.deadline_misses_per_sink.is_empty());
+}
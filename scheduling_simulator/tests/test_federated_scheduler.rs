@@ -0,0 +1,119 @@
+use std::collections::BTreeSet;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        federated_scheduler::{allocate_cores, FederatedAllocationError, FederatedScheduler},
+    },
+    task::dag::{Node, DAG},
+};
+
+mod util;
+use util::{create_sequential_dag_set1, create_simple_dag, create_single_node_dag_set};
+
+fn create_heavy_and_light_dag_set() -> Vec<Graph<Node, i32>> {
+    // volume: 18, critical path length: 6, deadline: 10 -> ceil(12 / 4) = 3 dedicated cores
+    let mut heavy_dag = create_simple_dag();
+    heavy_dag.set_param_to_all_nodes("relative_deadline", 10);
+    let mut light_dag = create_sequential_dag_set1().remove(0);
+    light_dag.set_param_to_all_nodes("dag_id", 1);
+
+    vec![heavy_dag, light_dag]
+}
+
+#[test]
+fn test_allocate_cores_normal() {
+    let dag_set = create_heavy_and_light_dag_set();
+    assert_eq!(
+        allocate_cores(&dag_set, 6),
+        Ok(vec![vec![0, 1, 2], vec![3, 4, 5]])
+    );
+}
+
+#[test]
+fn test_allocate_cores_insufficient_cores() {
+    let dag_set = create_heavy_and_light_dag_set();
+    assert_eq!(
+        allocate_cores(&dag_set, 3),
+        Err(FederatedAllocationError::InsufficientCores {
+            required: 4,
+            num_cores: 3,
+        })
+    );
+    assert!(FederatedScheduler::try_new(&dag_set, &HomogeneousProcessor::new(3)).is_err());
+}
+
+#[test]
+fn test_allocate_cores_critical_path_exceeds_deadline() {
+    let mut dag_set = create_heavy_and_light_dag_set();
+    dag_set[0].set_param_to_all_nodes("relative_deadline", 5);
+    assert_eq!(
+        allocate_cores(&dag_set, 8),
+        Err(FederatedAllocationError::CriticalPathExceedsDeadline {
+            dag_id: 0,
+            critical_path_length: 6,
+            deadline: 5,
+        })
+    );
+}
+
+#[test]
+fn test_allocate_cores_heavy_chain() {
+    // A chain longer than its period is heavy with C = L, and needs a core for each of its ceil(60 / 20) = 3 jobs in
+    // flight.
+    let dag_set =
+        create_single_node_dag_set(&[vec![("execution_time", 30), ("relative_deadline", 60)]]);
+    assert_eq!(allocate_cores(&dag_set, 3), Ok(vec![vec![0, 1, 2]]));
+
+    let mut scheduler = FederatedScheduler::new(&dag_set, &HomogeneousProcessor::new(3));
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        100,
+    );
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    assert_eq!(log.dag_set_log[0].response_times_per_sink[&0], vec![30; 4]);
+}
+
+#[test]
+fn test_federated_scheduler_normal() {
+    let dag_set = create_heavy_and_light_dag_set();
+    let processor = HomogeneousProcessor::new(4);
+    let mut scheduler = FederatedScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        20,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    assert_eq!(log.dag_set_log[0].response_times_per_sink[&6], vec![8, 8]);
+    assert_eq!(
+        log.dag_set_log[1].response_times_per_sink[&0],
+        vec![1, 1, 1, 1]
+    );
+}
+
+#[test]
+fn test_try_new_affinity_conflict() {
+    // The light DAG shares cores 3 to 5, so a node pinned to core 0 cannot run.
+    let mut dag_set = create_heavy_and_light_dag_set();
+    let light_source = dag_set[1].get_source();
+    dag_set[1][light_source].affinity = Some(BTreeSet::from([0]));
+    let err = FederatedScheduler::try_new(&dag_set, &HomogeneousProcessor::new(6))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        FederatedAllocationError::AffinityConflict {
+            dag_id: 1,
+            node_id: dag_set[1][light_source].id,
+        }
+    );
+}
@@ -1,5 +1,6 @@
 mod util;
 use scheduling_simulator::{
+    log::{DeadlineMiss, ExecutionInterval, IntervalEnd},
     processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
     scheduler::{
         dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
@@ -63,3 +64,143 @@ fn test_sequential_rm_scheduler_missed() {
     let rt1 = log.dag_set_log[1].response_times_per_sink[&0].clone();
     assert_eq!(rt1, vec![8]);
 }
+
+#[test]
+fn test_sequential_rm_scheduler_continue_after_missed() {
+    let mut dag_set = create_sequential_dag_set0();
+    for dag in dag_set.iter_mut() {
+        let dag_period = dag.get_dag_param("period");
+        for node in dag.node_weights_mut() {
+            node.params.insert("priority".to_string(), dag_period);
+        }
+    }
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.get_config_mut().continue_after_deadline_miss = true;
+    let schedule_length = scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        28,
+    );
+
+    assert_eq!(schedule_length, 28);
+    let log = scheduler.get_log();
+    assert!(log.deadline_missed);
+    assert_eq!(log.missed_job_id, Some(0));
+    let rt0 = log.dag_set_log[0].response_times_per_sink[&0].clone();
+    assert_eq!(rt0, vec![2, 2, 2, 2, 2, 2]);
+    let rt1 = log.dag_set_log[1].response_times_per_sink[&0].clone();
+    assert_eq!(rt1, vec![8, 7, 6, 7]);
+    assert_eq!(
+        log.dag_set_log[1].deadline_misses_per_sink[&0],
+        vec![DeadlineMiss {
+            job_id: 0,
+            tardiness: 1,
+            finished: true,
+        }]
+    );
+    assert!(log.dag_set_log[0].deadline_misses_per_sink.is_empty());
+    assert_eq!(log.miss_ratio, 0.1);
+    assert_eq!(log.max_tardiness, 1);
+}
+
+#[test]
+fn test_sequential_rm_scheduler_unfinished_missed() {
+    let mut dag_set = create_sequential_dag_set0();
+    dag_set.truncate(1);
+    for node in dag_set[0].node_weights_mut() {
+        node.params.insert("execution_time".to_string(), 6);
+        node.params.insert("priority".to_string(), 0);
+    }
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.get_config_mut().continue_after_deadline_miss = true;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        21,
+    );
+
+    let log = scheduler.get_log();
+    let tardiness = log.dag_set_log[0].deadline_misses_per_sink[&0]
+        .iter()
+        .map(|miss| (miss.tardiness, miss.finished))
+        .collect::<Vec<_>>();
+    assert_eq!(tardiness, vec![(1, true), (2, true), (3, true), (1, false)]);
+    assert_eq!(log.miss_ratio, 1.0);
+    assert_eq!(log.max_tardiness, 3);
+    assert_eq!(log.tardiness_percentiles["p50"], 1);
+    assert_eq!(log.tardiness_percentiles["p99"], 3);
+}
+
+#[test]
+fn test_sequential_rm_scheduler_trace() {
+    let mut dag_set = create_sequential_dag_set1();
+    for dag in dag_set.iter_mut() {
+        let dag_period = dag.get_dag_param("period");
+        for node in dag.node_weights_mut() {
+            node.params.insert("priority".to_string(), dag_period);
+        }
+    }
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.get_config_mut().record_trace = true;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        12,
+    );
+
+    let interval = |dag_id, job_id, start, end, end_reason| ExecutionInterval {
+        dag_id,
+        job_id,
+        node_id: 0,
+        start,
+        end,
+        end_reason,
+    };
+    assert_eq!(
+        scheduler.get_log().get_trace(0),
+        [
+            interval(0, 0, 0, 1, IntervalEnd::Completed),
+            interval(1, 0, 1, 5, IntervalEnd::Completed),
+            interval(0, 1, 5, 6, IntervalEnd::Completed),
+            interval(1, 1, 7, 10, IntervalEnd::Preempted),
+            interval(0, 2, 10, 11, IntervalEnd::Completed),
+            interval(1, 1, 11, 12, IntervalEnd::Completed),
+        ]
+    );
+}
+
+#[test]
+fn test_sequential_rm_scheduler_offset() {
+    let mut dag_set = create_sequential_dag_set1();
+    for dag in dag_set.iter_mut() {
+        let dag_period = dag.get_dag_param("period");
+        for node in dag.node_weights_mut() {
+            node.params.insert("priority".to_string(), dag_period);
+        }
+    }
+    dag_set[1].set_param_to_all_nodes("offset", 3);
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        10,
+    );
+
+    // DAG 1 arrives at 3 and is preempted by DAG 0 at 5.
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    assert_eq!(log.dag_set_log[0].response_times_per_sink[&0], vec![1, 1]);
+    assert_eq!(log.dag_set_log[1].response_times_per_sink[&0], vec![5]);
+}
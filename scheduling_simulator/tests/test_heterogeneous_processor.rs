@@ -0,0 +1,43 @@
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::heterogeneous::HeterogeneousProcessor,
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::Node,
+};
+
+mod util;
+use util::create_single_node_dag_set;
+
+// Single-node DAGs with the same deadline, so they are allocated in the order of dag_id.
+fn create_dag_set(num_dags: usize) -> Vec<Graph<Node, i32>> {
+    create_single_node_dag_set(&vec![vec![("execution_time", 4)]; num_dags])
+}
+
+fn schedule(core_speeds: &[f64], num_dags: usize) -> Vec<i32> {
+    let processor = HeterogeneousProcessor::new_with_speeds(core_speeds, Default::default());
+    let mut scheduler = GlobalEDFScheduler::new(&create_dag_set(num_dags), &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        20,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    log.dag_set_log
+        .iter()
+        .map(|dag_log| dag_log.response_times_per_sink[&0][0])
+        .collect()
+}
+
+#[test]
+fn test_heterogeneous_processor_prefers_faster_core() {
+    // DAG 0 runs on the big core and DAG 1 on the LITTLE core.
+    assert_eq!(schedule(&[0.5, 1.0], 2), vec![4, 8]);
+    // A single DAG always runs on the big core.
+    assert_eq!(schedule(&[0.5, 0.5, 2.0], 1), vec![2]);
+}
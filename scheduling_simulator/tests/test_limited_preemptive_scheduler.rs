@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    log::DAGSetSchedulerLog,
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptionMode, PreemptiveType},
+        fixed_priority_scheduler::FixedPriorityScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+// DAG 0 is released every 4 time units and has the highest priority.
+// DAG 1 and DAG 2 can be preempted every 4 and 2 time units, respectively.
+fn create_limited_preemptive_dag_set() -> Vec<Graph<Node, i32>> {
+    let mut dag_set = Vec::new();
+
+    for (dag_id, priority, period, execution_time, npr_length) in [
+        (0, 1, 4, 1, None),
+        (1, 3, 12, 6, Some(4)),
+        (2, 2, 12, 6, Some(2)),
+    ] {
+        let mut dag = Graph::<Node, i32>::new();
+        let mut params = BTreeMap::from([
+            ("period".to_string(), period),
+            ("relative_deadline".to_string(), period),
+            ("execution_time".to_string(), execution_time),
+            ("priority".to_string(), priority),
+        ]);
+        if let Some(npr_length) = npr_length {
+            params.insert("npr_length".to_string(), npr_length);
+        }
+        dag.add_node(Node::new(0, params));
+        dag.set_param_to_all_nodes("dag_id", dag_id);
+        dag_set.push(dag);
+    }
+
+    dag_set
+}
+
+fn schedule(mode: PreemptionMode) -> DAGSetSchedulerLog {
+    let dag_set = create_limited_preemptive_dag_set();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::LimitedPreemptive {
+            key: "priority".to_string(),
+            mode,
+        },
+        12,
+    );
+
+    scheduler.get_log().clone()
+}
+
+#[test]
+fn test_limited_preemptive_lazy() {
+    let log = schedule(PreemptionMode::Lazy);
+
+    // At time 4, DAG 0 waits until DAG 1, the lowest-priority running node, reaches its preemption point.
+    assert!(!log.deadline_missed);
+    assert_eq!(
+        log.dag_set_log[0].response_times_per_sink[&0],
+        vec![1, 2, 1]
+    );
+    assert_eq!(log.dag_set_log[1].response_times_per_sink[&0], vec![8]);
+    assert_eq!(log.dag_set_log[2].response_times_per_sink[&0], vec![6]);
+    assert_eq!(log.dag_set_log[0].worst_blocking_time_per_node[&0], 1);
+    assert_eq!(log.max_blocking_time, 1);
+}
+
+#[test]
+fn test_limited_preemptive_eager() {
+    let log = schedule(PreemptionMode::Eager);
+
+    // At time 4, DAG 0 preempts DAG 2, which is at its preemption point.
+    // DAG 2 is then blocked by DAG 1 until DAG 0 finishes.
+    assert!(!log.deadline_missed);
+    assert_eq!(
+        log.dag_set_log[0].response_times_per_sink[&0],
+        vec![1, 1, 1]
+    );
+    assert_eq!(log.dag_set_log[1].response_times_per_sink[&0], vec![7]);
+    assert_eq!(log.dag_set_log[2].response_times_per_sink[&0], vec![7]);
+    assert!(log.dag_set_log[0].worst_blocking_time_per_node.is_empty());
+    assert_eq!(log.dag_set_log[2].worst_blocking_time_per_node[&0], 1);
+}
@@ -0,0 +1,119 @@
+use petgraph::Graph;
+use scheduling_simulator::{
+    log::DAGSetSchedulerLog,
+    processor::{
+        heterogeneous::HeterogeneousProcessor, homogeneous::HomogeneousProcessor,
+        processor_interface::Processor,
+    },
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        edzl_scheduler::EDZLScheduler,
+        llf_scheduler::GlobalLLFScheduler,
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::Node,
+};
+
+mod util;
+use util::{create_single_node_dag_set, create_zero_laxity_dag_set};
+
+fn assert_zero_laxity_dag_set_schedulable(log: &DAGSetSchedulerLog) {
+    assert!(!log.deadline_missed);
+    assert_eq!(log.dag_set_log[0].response_times_per_sink[&0], vec![2, 2]);
+    assert_eq!(log.dag_set_log[1].response_times_per_sink[&0], vec![3, 3]);
+    assert_eq!(log.dag_set_log[2].response_times_per_sink[&0], vec![4]);
+}
+
+#[test]
+fn test_edf_scheduler_zero_laxity_missed() {
+    let dag_set = create_zero_laxity_dag_set();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        6,
+    );
+
+    assert!(scheduler.get_log().deadline_missed);
+}
+
+#[test]
+fn test_llf_scheduler_normal() {
+    let dag_set = create_zero_laxity_dag_set();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalLLFScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "latest_start_time".to_string(),
+        },
+        6,
+    );
+
+    assert_zero_laxity_dag_set_schedulable(scheduler.get_log());
+}
+
+#[test]
+fn test_edzl_scheduler_normal() {
+    let dag_set = create_zero_laxity_dag_set();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = EDZLScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "edzl_priority".to_string(),
+        },
+        6,
+    );
+
+    assert_zero_laxity_dag_set_schedulable(scheduler.get_log());
+}
+
+// On a big and a LITTLE core, LLF and EDZL must take the remaining work of node 1 at the LITTLE core's speed to see
+// that it has no laxity, and that of node 2 at the speed of the core it runs on.
+fn schedule_heterogeneous(
+    scheduler: &mut impl DAGSetSchedulerBase<HeterogeneousProcessor>,
+    key: &str,
+) -> Vec<i32> {
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: key.to_string(),
+        },
+        4,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    log.dag_set_log
+        .iter()
+        .map(|dag_log| dag_log.response_times_per_sink[&0][0])
+        .collect()
+}
+
+fn create_heterogeneous_dag_set() -> Vec<Graph<Node, i32>> {
+    create_single_node_dag_set(&[
+        vec![("execution_time", 1), ("relative_deadline", 3)],
+        vec![("execution_time", 3), ("relative_deadline", 4)],
+        vec![("execution_time", 2), ("relative_deadline", 4)],
+    ])
+}
+
+#[test]
+fn test_llf_scheduler_heterogeneous() {
+    let processor = HeterogeneousProcessor::new_with_speeds(&[1.0, 0.5], Default::default());
+    let mut scheduler = GlobalLLFScheduler::new(&create_heterogeneous_dag_set(), &processor);
+    assert_eq!(
+        schedule_heterogeneous(&mut scheduler, "latest_start_time"),
+        vec![3, 4, 4]
+    );
+}
+
+#[test]
+fn test_edzl_scheduler_heterogeneous() {
+    let processor = HeterogeneousProcessor::new_with_speeds(&[1.0, 0.5], Default::default());
+    let mut scheduler = EDZLScheduler::new(&create_heterogeneous_dag_set(), &processor);
+    assert_eq!(
+        schedule_heterogeneous(&mut scheduler, "edzl_priority"),
+        vec![2, 4, 4]
+    );
+}
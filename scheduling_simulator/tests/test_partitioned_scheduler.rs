@@ -0,0 +1,188 @@
+use std::collections::BTreeSet;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        fixed_priority_scheduler::FixedPriorityScheduler,
+        partitioning::{
+            partition_dag_set, partition_dag_set_with_speeds, BinPackingHeuristic, PartitioningUnit,
+        },
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+mod util;
+use util::{create_sequential_dag_set0, create_simple_dag};
+
+/// The core that each DAG is partitioned to, as a bit mask.
+fn get_affinities(dag_set: &[Graph<Node, i32>]) -> Vec<i32> {
+    dag_set
+        .iter()
+        .map(|dag| 1 << dag.get_dag_param("partitioned_core"))
+        .collect()
+}
+
+#[test]
+fn test_partition_dag_set_heuristics() {
+    // Utilizations: dag0 = 0.4, dag1 = 0.57
+    let mut dag_set = create_sequential_dag_set0();
+    partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    assert_eq!(get_affinities(&dag_set), vec![0b01, 0b01]);
+
+    partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Bfd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    assert_eq!(get_affinities(&dag_set), vec![0b01, 0b01]);
+
+    let core_utilizations = partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Wfd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    assert_eq!(get_affinities(&dag_set), vec![0b10, 0b01]);
+    assert_eq!(core_utilizations, vec![4.0 / 7.0, 0.4]);
+}
+
+#[test]
+fn test_partition_dag_set_affinity() {
+    // dag1 is only allowed on core 1, so FFD no longer packs both DAGs on core 0.
+    let mut dag_set = create_sequential_dag_set0();
+    for node in dag_set[1].node_weights_mut() {
+        node.affinity = Some(BTreeSet::from([1]));
+    }
+    partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    assert_eq!(get_affinities(&dag_set), vec![0b01, 0b10]);
+
+    // The affinity is kept, so the DAG cannot be partitioned onto a single core.
+    assert!(partition_dag_set(
+        &mut dag_set,
+        1,
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Dag,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_partition_dag_set_with_speeds() {
+    // dag1 (0.57) exceeds the capacity of core 0 at speed 0.5, so FFD no longer packs both DAGs on core 0.
+    let mut dag_set = create_sequential_dag_set0();
+    let core_utilizations = partition_dag_set_with_speeds(
+        &mut dag_set,
+        &[0.5, 1.0],
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    assert_eq!(get_affinities(&dag_set), vec![0b01, 0b10]);
+    assert_eq!(core_utilizations, vec![0.4, 4.0 / 7.0]);
+}
+
+#[test]
+fn test_partition_dag_set_node_unit() {
+    let mut dag = create_simple_dag();
+    dag.set_param_to_all_nodes("relative_deadline", 10);
+    let mut dag_set = vec![dag];
+    let core_utilizations = partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Wfd,
+        PartitioningUnit::Node,
+    )
+    .unwrap();
+
+    for core_utilization in core_utilizations {
+        assert!((core_utilization - 0.9).abs() < 1e-6);
+    }
+    assert!(partition_dag_set(
+        &mut dag_set,
+        1,
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Node,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_partitioned_edf_scheduler() {
+    // Both DAGs fit on core 0, so the schedule is the same as the uniprocessor EDF.
+    let mut dag_set = create_sequential_dag_set0();
+    partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Ffd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        26,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    let rt0 = log.dag_set_log[0].response_times_per_sink[&0].clone();
+    assert_eq!(rt0, vec![2, 3, 4, 2, 2]);
+    let rt1 = log.dag_set_log[1].response_times_per_sink[&0].clone();
+    assert_eq!(rt1, vec![6, 5, 6, 5]);
+}
+
+#[test]
+fn test_partitioned_rm_scheduler() {
+    let mut dag_set = create_sequential_dag_set0();
+    for dag in dag_set.iter_mut() {
+        let dag_period = dag.get_dag_param("period");
+        dag.set_param_to_all_nodes("priority", dag_period);
+    }
+    partition_dag_set(
+        &mut dag_set,
+        2,
+        BinPackingHeuristic::Wfd,
+        PartitioningUnit::Dag,
+    )
+    .unwrap();
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        28,
+    );
+
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    assert_eq!(
+        log.dag_set_log[0].response_times_per_sink[&0],
+        vec![2, 2, 2, 2, 2, 2]
+    );
+    assert_eq!(
+        log.dag_set_log[1].response_times_per_sink[&0],
+        vec![4, 4, 4, 4]
+    );
+}
@@ -7,7 +7,7 @@ use scheduling_simulator::{
 };
 
 mod util;
-use util::{create_multi_sink_dag_set, create_sequential_dag_set0};
+use util::{create_multi_sink_dag_set, create_sequential_dag_set0, scale_dag_set_time};
 
 #[test]
 fn test_sequential_edf_scheduler() {
@@ -29,6 +29,29 @@ fn test_sequential_edf_scheduler() {
     assert_eq!(rt1, vec![6, 5, 6, 5]);
 }
 
+#[test]
+fn test_sequential_edf_scheduler_large_time_scale() {
+    const SCALE: i32 = 100000;
+    let mut dag_set = create_sequential_dag_set0();
+    scale_dag_set_time(&mut dag_set, SCALE);
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    let schedule_length = scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        26 * SCALE,
+    );
+
+    assert_eq!(schedule_length, 26 * SCALE);
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    let rt0 = log.dag_set_log[0].response_times_per_sink[&0].clone();
+    assert_eq!(rt0, [2, 3, 4, 2, 2].map(|rt| rt * SCALE));
+    let rt1 = log.dag_set_log[1].response_times_per_sink[&0].clone();
+    assert_eq!(rt1, [6, 5, 6, 5].map(|rt| rt * SCALE));
+}
+
 #[test]
 fn test_multi_sink_dag_set_edf_scheduler() {
     let dag_set = create_multi_sink_dag_set();
@@ -0,0 +1,272 @@
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType, ResourceProtocol},
+        edzl_scheduler::EDZLScheduler,
+        fixed_priority_scheduler::FixedPriorityScheduler,
+        llf_scheduler::GlobalLLFScheduler,
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::Node,
+};
+
+mod util;
+use util::create_single_node_dag_set;
+
+// (priority, offset, execution_time, critical section as (`cs.<resource>`, length)) of a single-node DAG
+type Task = (i32, i32, i32, Option<(&'static str, i32)>);
+
+fn create_dag_set(tasks: &[Task]) -> Vec<Graph<Node, i32>> {
+    let params_per_dag = tasks
+        .iter()
+        .map(|&(priority, offset, execution_time, critical_section)| {
+            let mut params = vec![
+                ("execution_time", execution_time),
+                ("offset", offset),
+                ("priority", priority),
+            ];
+            if let Some(critical_section) = critical_section {
+                params.push(critical_section);
+            }
+            params
+        })
+        .collect::<Vec<_>>();
+    create_single_node_dag_set(&params_per_dag)
+}
+
+// Returns the response times and the worst blocking times of the DAGs, and the total overhead of the processor.
+fn get_results(
+    scheduler: &impl DAGSetSchedulerBase<HomogeneousProcessor>,
+) -> (Vec<i32>, Vec<i32>, i32) {
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    let response_times = log
+        .dag_set_log
+        .iter()
+        .map(|dag_log| dag_log.response_times_per_sink[&0][0])
+        .collect();
+    let blocking_times = log
+        .dag_set_log
+        .iter()
+        .map(|dag_log| *dag_log.worst_blocking_time_per_node.get(&0).unwrap_or(&0))
+        .collect();
+    let total_overhead = scheduler
+        .get_processor()
+        .get_total_overhead_times()
+        .iter()
+        .sum();
+    (response_times, blocking_times, total_overhead)
+}
+
+fn schedule_dag_set(
+    dag_set: &[Graph<Node, i32>],
+    num_cores: usize,
+    resource_protocol: ResourceProtocol,
+) -> (Vec<i32>, Vec<i32>, i32) {
+    let processor = HomogeneousProcessor::new(num_cores);
+    let mut scheduler = FixedPriorityScheduler::new(dag_set, &processor);
+    scheduler.get_config_mut().resource_protocol = resource_protocol;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        20,
+    );
+    get_results(&scheduler)
+}
+
+fn schedule(
+    tasks: &[Task],
+    num_cores: usize,
+    resource_protocol: ResourceProtocol,
+) -> (Vec<i32>, Vec<i32>, i32) {
+    schedule_dag_set(&create_dag_set(tasks), num_cores, resource_protocol)
+}
+
+// The low-priority DAG 0 holds `r` when the high-priority DAG 1 and the medium-priority DAG 2 are released.
+const PRIORITY_INVERSION: [Task; 3] = [
+    (3, 0, 4, Some(("cs.r", 3))),
+    (1, 1, 2, Some(("cs.r", 1))),
+    (2, 1, 5, None),
+];
+
+#[test]
+fn test_mutual_exclusion_without_protocol() {
+    // DAG 2 preempts DAG 0, so DAG 1 waits until DAG 2 completes and DAG 0 releases `r`.
+    let (response_times, blocking_times, _) =
+        schedule(&PRIORITY_INVERSION, 1, ResourceProtocol::None);
+    assert_eq!(response_times, vec![11, 9, 5]);
+    assert_eq!(blocking_times[1], 7);
+}
+
+#[test]
+fn test_priority_inheritance_and_ceiling() {
+    // DAG 0 runs at the priority of DAG 1 until it releases `r`, so DAG 2 cannot preempt it.
+    for resource_protocol in [ResourceProtocol::Pip, ResourceProtocol::Pcp] {
+        let (response_times, blocking_times, _) =
+            schedule(&PRIORITY_INVERSION, 1, resource_protocol);
+        assert_eq!(response_times, vec![11, 4, 9]);
+        assert_eq!(blocking_times[1], 2);
+    }
+}
+
+#[test]
+fn test_priority_ceiling_blocks_below_system_ceiling() {
+    // The ceiling of `r` is the priority of DAG 1, which is released later. DAG 2 only uses `s`, but under PCP it
+    // waits for DAG 0 to release `r` at time 3 although a core is idle.
+    let tasks = [
+        (3, 0, 4, Some(("cs.r", 3))),
+        (1, 10, 1, Some(("cs.r", 1))),
+        (2, 1, 2, Some(("cs.s", 1))),
+    ];
+    let (response_times, blocking_times, _) = schedule(&tasks, 2, ResourceProtocol::Pip);
+    assert_eq!(response_times, vec![4, 1, 2]);
+    assert_eq!(blocking_times[2], 0);
+
+    let (response_times, blocking_times, _) = schedule(&tasks, 2, ResourceProtocol::Pcp);
+    assert_eq!(response_times, vec![4, 1, 4]);
+    assert_eq!(blocking_times[2], 2);
+}
+
+#[test]
+fn test_mpcp_runs_critical_sections_above_all_priorities() {
+    // DAG 0 holds `r`, whose ceiling is the priority of DAG 2. Under PCP, DAG 0 blocks no node, so the higher-priority
+    // DAG 1 preempts it. Under MPCP, DAG 1 waits until DAG 0 releases `r` at time 3.
+    let tasks = [
+        (3, 0, 4, Some(("cs.r", 3))),
+        (1, 1, 2, None),
+        (2, 10, 1, Some(("cs.r", 1))),
+    ];
+    let (response_times, _, _) = schedule(&tasks, 1, ResourceProtocol::Pcp);
+    assert_eq!(response_times, vec![6, 2, 1]);
+
+    let (response_times, _, _) = schedule(&tasks, 1, ResourceProtocol::Mpcp);
+    assert_eq!(response_times, vec![6, 4, 1]);
+}
+
+#[test]
+fn test_priority_ceiling_under_edf() {
+    // The ceiling of `r` is the relative deadline 5 of DAG 1, which is released later. While DAG 0 holds `r`, DAG 2
+    // with the relative deadline 10 waits for `s`, but DAG 3 with 4 acquires it on the idle core.
+    let dag_set = create_single_node_dag_set(&[
+        vec![("execution_time", 4), ("cs.r", 3)],
+        vec![
+            ("execution_time", 1),
+            ("relative_deadline", 5),
+            ("offset", 10),
+            ("cs.r", 1),
+        ],
+        vec![
+            ("execution_time", 2),
+            ("relative_deadline", 10),
+            ("offset", 1),
+            ("cs.s", 1),
+        ],
+        vec![
+            ("execution_time", 1),
+            ("relative_deadline", 4),
+            ("offset", 2),
+            ("cs.s", 1),
+        ],
+    ]);
+    let processor = HomogeneousProcessor::new(2);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.get_config_mut().resource_protocol = ResourceProtocol::Pcp;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        20,
+    );
+
+    let (response_times, blocking_times, _) = get_results(&scheduler);
+    assert_eq!(response_times, vec![4, 1, 4, 1]);
+    assert_eq!(blocking_times, vec![0, 0, 2, 0]);
+}
+
+#[test]
+fn test_protocols_under_llf_and_edzl() {
+    // DAG 0 holds `r` when DAG 1 with the tighter deadline is released, on a single core, so DAG 1 waits until DAG 0
+    // releases `r` at time 3.
+    let dag_set = create_single_node_dag_set(&[
+        vec![
+            ("execution_time", 4),
+            ("relative_deadline", 10),
+            ("cs.r", 3),
+        ],
+        vec![
+            ("execution_time", 2),
+            ("relative_deadline", 6),
+            ("offset", 1),
+            ("cs.r", 1),
+        ],
+    ]);
+    for resource_protocol in [
+        ResourceProtocol::Pip,
+        ResourceProtocol::Pcp,
+        ResourceProtocol::Mpcp,
+        ResourceProtocol::Mrsp,
+    ] {
+        let processor = HomogeneousProcessor::new(1);
+        let mut scheduler = GlobalLLFScheduler::new(&dag_set, &processor);
+        scheduler.get_config_mut().resource_protocol = resource_protocol;
+        scheduler.schedule(
+            PreemptiveType::Preemptive {
+                key: "latest_start_time".to_string(),
+            },
+            20,
+        );
+        let (response_times, blocking_times, _) = get_results(&scheduler);
+        assert_eq!(response_times, vec![6, 4]);
+        assert_eq!(blocking_times, vec![0, 2]);
+
+        let mut scheduler = EDZLScheduler::new(&dag_set, &processor);
+        scheduler.get_config_mut().resource_protocol = resource_protocol;
+        scheduler.schedule(
+            PreemptiveType::Preemptive {
+                key: "edzl_priority".to_string(),
+            },
+            20,
+        );
+        let (response_times, blocking_times, _) = get_results(&scheduler);
+        assert_eq!(response_times, vec![6, 4]);
+        assert_eq!(blocking_times, vec![0, 2]);
+    }
+}
+
+#[test]
+fn test_resources_are_held_only_during_their_critical_sections() {
+    // DAG 0 holds `a` until time 2 and then needs `b`, which the lower-priority DAG 1 acquired at time 0. DAG 0 waits
+    // off its core until DAG 1 releases `b` at time 3.
+    let dag_set = create_single_node_dag_set(&[
+        vec![
+            ("execution_time", 5),
+            ("priority", 1),
+            ("cs.a", 2),
+            ("cs.b", 2),
+        ],
+        vec![("execution_time", 3), ("priority", 2), ("cs.b", 3)],
+    ]);
+    let (response_times, blocking_times, _) = schedule_dag_set(&dag_set, 2, ResourceProtocol::None);
+    assert_eq!(response_times, vec![6, 3]);
+    assert_eq!(blocking_times, vec![1, 0]);
+}
+
+#[test]
+fn test_mrsp_spins_on_another_core() {
+    // DAG 1 starts on the idle core and spins until DAG 0 releases `r` at time 3.
+    let tasks = [(2, 0, 4, Some(("cs.r", 3))), (1, 1, 2, Some(("cs.r", 1)))];
+    let (response_times, blocking_times, total_overhead) =
+        schedule(&tasks, 2, ResourceProtocol::Mrsp);
+    assert_eq!(response_times, vec![4, 4]);
+    assert_eq!(blocking_times[1], 2);
+    assert_eq!(total_overhead, 2);
+
+    // Without the protocol, DAG 1 waits without occupying the core.
+    let (response_times, blocking_times, total_overhead) =
+        schedule(&tasks, 2, ResourceProtocol::None);
+    assert_eq!(response_times, vec![4, 4]);
+    assert_eq!(blocking_times[1], 2);
+    assert_eq!(total_overhead, 0);
+}
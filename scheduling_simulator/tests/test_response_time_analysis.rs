@@ -0,0 +1,93 @@
+mod util;
+use std::collections::BTreeMap;
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    analysis::response_time::{analyze_response_times, AnalyzedPolicy},
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        fixed_priority_scheduler::FixedPriorityScheduler,
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::{Node, DAG},
+};
+
+use util::{create_multi_sink_dag_set, create_simple_dag};
+
+fn create_dag_set() -> Vec<Graph<Node, i32>> {
+    let mut dag_set = create_multi_sink_dag_set();
+    let mut simple_dag = create_simple_dag();
+    simple_dag.set_param_to_all_nodes("dag_id", 2);
+    simple_dag.set_param_to_all_nodes("relative_deadline", 30);
+    simple_dag.set_param(simple_dag.get_source(), "period", 30);
+    dag_set.push(simple_dag);
+    dag_set
+}
+
+fn assert_bounds_hold(
+    response_times: &[BTreeMap<usize, i32>],
+    bounds: &[BTreeMap<usize, Option<i32>>],
+) {
+    for (response_times, bounds) in response_times.iter().zip(bounds) {
+        for (sink_id, response_time) in response_times {
+            let bound = bounds[sink_id].expect("The sink must be bounded.");
+            assert!(*response_time <= bound, "{} > {}", response_time, bound);
+        }
+    }
+}
+
+fn get_worst_response_times<T: DAGSetSchedulerBase<HomogeneousProcessor>>(
+    scheduler: &T,
+) -> Vec<BTreeMap<usize, i32>> {
+    scheduler
+        .get_log()
+        .dag_set_log
+        .iter()
+        .map(|dag_log| {
+            dag_log
+                .response_times_per_sink
+                .iter()
+                .map(|(&sink_id, response_times)| (sink_id, *response_times.iter().max().unwrap()))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_edf_bounds_hold_in_simulation() {
+    let dag_set = create_dag_set();
+    let processor = HomogeneousProcessor::new(3);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        300,
+    );
+    assert!(!scheduler.get_log().deadline_missed);
+
+    let bounds = analyze_response_times(&dag_set, 3, AnalyzedPolicy::Edf);
+    assert_bounds_hold(&get_worst_response_times(&scheduler), &bounds);
+}
+
+#[test]
+fn test_rm_bounds_hold_in_simulation() {
+    let mut dag_set = create_dag_set();
+    for dag in dag_set.iter_mut() {
+        let dag_period = dag.get_dag_param("period");
+        dag.set_param_to_all_nodes("priority", dag_period);
+    }
+    let processor = HomogeneousProcessor::new(3);
+    let mut scheduler = FixedPriorityScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        },
+        300,
+    );
+    assert!(!scheduler.get_log().deadline_missed);
+
+    let bounds = analyze_response_times(&dag_set, 3, AnalyzedPolicy::Rm);
+    assert_bounds_hold(&get_worst_response_times(&scheduler), &bounds);
+}
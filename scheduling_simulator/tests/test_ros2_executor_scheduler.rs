@@ -0,0 +1,134 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use petgraph::Graph;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        ros2_executor_scheduler::{ExecutorConfigError, ROS2ExecutorScheduler},
+    },
+    task::dag::{Node, DAG},
+};
+
+fn create_node(id: i32, params: &[(&str, i32)]) -> Node {
+    Node::new(
+        id,
+        params
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+// DAG 0: timer (execution_time_0) -> subscription (1), DAG 1: a single timer (execution_time_1).
+fn create_dag_set(
+    execution_time_0: i32,
+    execution_time_1: i32,
+    offset_1: i32,
+) -> Vec<Graph<Node, i32>> {
+    let mut dag0 = Graph::<Node, i32>::new();
+    let timer = dag0.add_node(create_node(
+        0,
+        &[("execution_time", execution_time_0), ("period", 20)],
+    ));
+    let subscription = dag0.add_node(create_node(
+        1,
+        &[("execution_time", 1), ("relative_deadline", 20)],
+    ));
+    dag0.add_edge(timer, subscription, 0);
+    dag0.set_param_to_all_nodes("dag_id", 0);
+
+    let mut dag1 = Graph::<Node, i32>::new();
+    dag1.add_node(create_node(
+        0,
+        &[
+            ("execution_time", execution_time_1),
+            ("period", 20),
+            ("relative_deadline", 20),
+            ("offset", offset_1),
+            ("dag_id", 1),
+        ],
+    ));
+
+    vec![dag0, dag1]
+}
+
+fn schedule(mut scheduler: ROS2ExecutorScheduler) -> Vec<i32> {
+    scheduler.schedule(PreemptiveType::NonPreemptive, 20);
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    vec![
+        log.dag_set_log[0].response_times_per_sink[&1][0],
+        log.dag_set_log[1].response_times_per_sink[&0][0],
+    ]
+}
+
+#[test]
+fn test_wait_set_snapshot() {
+    // The subscription becomes ready at 1, after the snapshot {timer 0, timer 1} is taken, so it waits for timer 1.
+    let processor = HomogeneousProcessor::new(1);
+    let scheduler = ROS2ExecutorScheduler::new(&create_dag_set(1, 3, 0), &processor);
+    assert_eq!(schedule(scheduler), vec![5, 4]);
+}
+
+#[test]
+fn test_timers_first() {
+    // At 2, the snapshot has timer 1 and the subscription of DAG 0, and the timer is taken first.
+    let processor = HomogeneousProcessor::new(1);
+    let scheduler = ROS2ExecutorScheduler::new(&create_dag_set(2, 1, 1), &processor);
+    assert_eq!(schedule(scheduler), vec![4, 2]);
+}
+
+#[test]
+fn test_multiple_executors() {
+    // Two single-threaded executors run the DAGs in parallel.
+    let mut dag_set = create_dag_set(1, 3, 0);
+    dag_set[1].set_param_to_all_nodes("executor", 1);
+    let processor = HomogeneousProcessor::new(2);
+    let scheduler =
+        ROS2ExecutorScheduler::try_new_with_executors(&dag_set, &processor, &[1, 1]).unwrap();
+    assert_eq!(schedule(scheduler), vec![2, 3]);
+}
+
+#[test]
+fn test_invalid_executors() {
+    let processor = HomogeneousProcessor::new(2);
+    let try_new = |dag_set: &[Graph<Node, i32>], num_threads: &[usize]| {
+        ROS2ExecutorScheduler::try_new_with_executors(dag_set, &processor, num_threads)
+            .err()
+            .unwrap()
+    };
+
+    let dag_set = create_dag_set(1, 3, 0);
+    assert_eq!(
+        try_new(&dag_set, &[2, 1]),
+        ExecutorConfigError::TooManyThreads {
+            num_threads: 3,
+            num_cores: 2,
+        }
+    );
+
+    let mut unknown_executor_dag_set = dag_set.clone();
+    unknown_executor_dag_set[1].set_param_to_all_nodes("executor", 1);
+    assert_eq!(
+        try_new(&unknown_executor_dag_set, &[2]),
+        ExecutorConfigError::UnknownExecutor {
+            dag_id: 1,
+            node_id: 0,
+            executor_i: 1,
+        }
+    );
+
+    // Executor 0 runs on core 0, so a node pinned to core 1 cannot run.
+    let mut affinity_dag_set = dag_set.clone();
+    let source_i = affinity_dag_set[1].get_source();
+    affinity_dag_set[1][source_i].affinity = Some(BTreeSet::from([1]));
+    assert_eq!(
+        try_new(&affinity_dag_set, &[1, 1]),
+        ExecutorConfigError::AffinityConflict {
+            dag_id: 1,
+            node_id: 0,
+            executor_i: 0,
+        }
+    );
+}
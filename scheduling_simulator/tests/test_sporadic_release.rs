@@ -0,0 +1,63 @@
+mod util;
+use scheduling_simulator::{
+    processor::{homogeneous::HomogeneousProcessor, processor_interface::Processor},
+    scheduler::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        proposed_edf_scheduler::GlobalEDFScheduler,
+    },
+    task::dag::DAG,
+};
+
+use util::create_sequential_dag_set1;
+
+fn schedule_with_jitter(seed: u64) -> Vec<i32> {
+    let mut dag_set = create_sequential_dag_set1();
+    dag_set.truncate(1);
+    dag_set[0].set_param_to_all_nodes("release_jitter", 3);
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.get_config_mut().seed = seed;
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        100,
+    );
+
+    assert!(!scheduler.get_log().deadline_missed);
+    scheduler.get_log().dag_set_log[0].response_times_per_sink[&0].clone()
+}
+
+#[test]
+fn test_release_jitter() {
+    // Response times are measured from the arrival time, so they include the release jitter.
+    let response_times = schedule_with_jitter(0);
+    assert_eq!(response_times.len(), 20);
+    assert!(response_times.iter().all(|rt| (1..=4).contains(rt)));
+    assert!(response_times.iter().any(|&rt| rt != response_times[0]));
+    assert_eq!(response_times, schedule_with_jitter(0));
+}
+
+#[test]
+fn test_inter_arrival_slack() {
+    let mut dag_set = create_sequential_dag_set1();
+    for dag in dag_set.iter_mut() {
+        dag.set_param_to_all_nodes("inter_arrival_slack", 2);
+    }
+
+    let processor = HomogeneousProcessor::new(1);
+    let mut scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    scheduler.schedule(
+        PreemptiveType::Preemptive {
+            key: "ref_absolute_deadline".to_string(),
+        },
+        100,
+    );
+
+    // Inter-arrival times of DAG 0 are in [5, 7], so 15 to 20 jobs arrive.
+    let log = scheduler.get_log();
+    assert!(!log.deadline_missed);
+    let num_jobs = log.dag_set_log[0].response_times_per_sink[&0].len();
+    assert!((15..=20).contains(&num_jobs));
+}
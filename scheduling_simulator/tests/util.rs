@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use std::collections::BTreeMap;
 
 use petgraph::Graph;
@@ -145,3 +147,57 @@ pub fn create_multi_sink_dag_set() -> Vec<Graph<Node, i32>> {
 
     dag_set
 }
+
+pub fn scale_dag_set_time(dag_set: &mut [Graph<Node, i32>], scale: i32) {
+    for dag in dag_set.iter_mut() {
+        for node in dag.node_weights_mut() {
+            for key in ["period", "relative_deadline", "execution_time"] {
+                if let Some(value) = node.params.get_mut(key) {
+                    *value *= scale;
+                }
+            }
+        }
+    }
+}
+
+// Under GEDF, the node of DAG 2 misses its deadline because it has to wait until the nodes with earlier deadlines
+// finish. Laxity-based schedulers meet all deadlines on 2 cores.
+pub fn create_zero_laxity_dag_set() -> Vec<Graph<Node, i32>> {
+    let mut dag_set = Vec::new();
+
+    for (dag_id, period, execution_time) in [(0, 3, 2), (1, 3, 2), (2, 4, 3)] {
+        let mut dag = Graph::<Node, i32>::new();
+        let params = BTreeMap::from([
+            ("period".to_string(), period),
+            ("relative_deadline".to_string(), period),
+            ("execution_time".to_string(), execution_time),
+        ]);
+        dag.add_node(Node::new(0, params));
+        dag.set_param_to_all_nodes("dag_id", dag_id);
+        dag_set.push(dag);
+    }
+
+    dag_set
+}
+
+/// Single-node DAGs, one per entry of `params_per_dag`, with `dag_id` set to the index of the entry.
+/// The period and the relative deadline are 20 unless given.
+pub fn create_single_node_dag_set(params_per_dag: &[Vec<(&str, i32)>]) -> Vec<Graph<Node, i32>> {
+    params_per_dag
+        .iter()
+        .enumerate()
+        .map(|(dag_id, params)| {
+            let mut node_params = BTreeMap::from([
+                ("period".to_string(), 20),
+                ("relative_deadline".to_string(), 20),
+                ("dag_id".to_string(), dag_id as i32),
+            ]);
+            for &(key, value) in params {
+                node_params.insert(key.to_string(), value);
+            }
+            let mut dag = Graph::<Node, i32>::new();
+            dag.add_node(Node::new(0, node_params));
+            dag
+        })
+        .collect()
+}